@@ -0,0 +1,172 @@
+use chrono::{DateTime, Local, Utc};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use crate::{export, DatabaseConnection};
+
+const CHECK_INTERVAL_SECS: u64 = 60;
+
+pub(crate) const ENABLED_KEY: &str = "auto_backup_enabled";
+const INTERVAL_HOURS_KEY: &str = "auto_backup_interval_hours";
+pub(crate) const DIR_KEY: &str = "auto_backup_dir";
+const KEEP_KEY: &str = "auto_backup_keep";
+pub(crate) const LAST_BACKUP_KEY: &str = "auto_backup_last_at";
+
+const DEFAULT_INTERVAL_HOURS: u32 = 24;
+const DEFAULT_KEEP: u32 = 7;
+
+// Backups are named by local time, so sorting the names sorts them by age
+const FILE_PREFIX: &str = "zentrack-backup-";
+const FILE_SUFFIX: &str = ".json";
+
+// Unix time of the last attempt, successful or not, so a failing backup waits a full interval
+// before it is retried instead of running every check
+static LAST_ATTEMPT: AtomicI64 = AtomicI64::new(0);
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AutoBackupSettings {
+    pub enabled: bool,
+    pub interval_hours: u32,
+    pub dir: Option<String>,
+    pub keep: u32, // newest backups kept in `dir`; older ones are deleted
+}
+
+pub fn load_settings(conn: &Connection) -> rusqlite::Result<AutoBackupSettings> {
+    let read_number = |key: &str, default: u32| -> rusqlite::Result<u32> {
+        Ok(crate::read_setting(conn, key)?
+            .and_then(|value| value.parse::<u32>().ok())
+            .filter(|value| *value > 0)
+            .unwrap_or(default))
+    };
+
+    Ok(AutoBackupSettings {
+        enabled: crate::read_setting(conn, ENABLED_KEY)?.as_deref() == Some("true"),
+        interval_hours: read_number(INTERVAL_HOURS_KEY, DEFAULT_INTERVAL_HOURS)?,
+        dir: crate::read_setting(conn, DIR_KEY)?.filter(|dir| !dir.trim().is_empty()),
+        keep: read_number(KEEP_KEY, DEFAULT_KEEP)?,
+    })
+}
+
+pub fn save_settings(conn: &Connection, settings: &AutoBackupSettings) -> Result<(), String> {
+    if settings.interval_hours == 0 {
+        return Err("Backup interval must be at least one hour".to_string());
+    }
+    if settings.keep == 0 {
+        return Err("At least one backup must be kept".to_string());
+    }
+
+    let dir = settings.dir.as_deref().map(str::trim).filter(|dir| !dir.is_empty());
+    if settings.enabled && crate::encryption::is_encrypted() {
+        return Err("Automatic backups are plain JSON and can't be turned on while the database is encrypted".to_string());
+    }
+    if settings.enabled {
+        let dir = dir.ok_or_else(|| "Choose a folder for automatic backups".to_string())?;
+        fs::create_dir_all(dir).map_err(|e| format!("Cannot use '{}': {}", dir, e))?;
+    }
+
+    let write = |key: &str, value: &str| crate::write_setting(conn, key, value).map_err(|e| e.to_string());
+    write(ENABLED_KEY, if settings.enabled { "true" } else { "false" })?;
+    write(INTERVAL_HOURS_KEY, &settings.interval_hours.to_string())?;
+    write(DIR_KEY, dir.unwrap_or(""))?;
+    write(KEEP_KEY, &settings.keep.to_string())?;
+
+    // New settings take effect on the next check rather than after a stale failure's wait
+    LAST_ATTEMPT.store(0, Ordering::Relaxed);
+    Ok(())
+}
+
+// RFC 3339 (UTC) time of the last successful automatic backup
+pub fn last_backup_time(conn: &Connection) -> rusqlite::Result<Option<String>> {
+    Ok(crate::read_setting(conn, LAST_BACKUP_KEY)?.filter(|time| !time.is_empty()))
+}
+
+pub fn start_backup_worker(db: DatabaseConnection) {
+    thread::spawn(move || loop {
+        // Nothing to back up until an encrypted database is unlocked
+        if !crate::encryption::is_locked() {
+            if let Err(err) = backup_if_due(&db) {
+                eprintln!("automatic backup failed: {}", err);
+            }
+        }
+        thread::sleep(Duration::from_secs(CHECK_INTERVAL_SECS));
+    });
+}
+
+fn backup_if_due(db: &DatabaseConnection) -> Result<(), String> {
+    let now = Utc::now();
+
+    let (settings, last_backup) = {
+        let conn = db.lock()?;
+        let settings = load_settings(&conn).map_err(|e| e.to_string())?;
+        let last_backup = last_backup_time(&conn)
+            .map_err(|e| e.to_string())?
+            .and_then(|time| DateTime::parse_from_rfc3339(&time).ok())
+            .map(|time| time.timestamp())
+            .unwrap_or(0);
+        (settings, last_backup)
+    };
+
+    let dir = match settings.dir {
+        Some(ref dir) if settings.enabled => PathBuf::from(dir),
+        _ => return Ok(()),
+    };
+
+    // Backups are plain JSON, so writing one would leave the encrypted data readable on disk
+    if crate::encryption::is_encrypted() {
+        return Ok(());
+    }
+
+    let last_run = last_backup.max(LAST_ATTEMPT.load(Ordering::Relaxed));
+    if now.timestamp() - last_run < i64::from(settings.interval_hours) * 3600 {
+        return Ok(());
+    }
+    LAST_ATTEMPT.store(now.timestamp(), Ordering::Relaxed);
+
+    // As in export_all, only the reads hold the lock
+    let data = {
+        let conn = db.lock()?;
+        crate::collect_export_data(&conn, |_, _| {})?
+    };
+
+    fs::create_dir_all(&dir).map_err(|e| format!("Cannot create '{}': {}", dir.display(), e))?;
+    let path = dir.join(format!("{}{}{}", FILE_PREFIX, Local::now().format("%Y%m%d-%H%M%S"), FILE_SUFFIX));
+    let path_str = path.to_str().ok_or_else(|| "Backup path is not valid UTF-8".to_string())?;
+    export::write_backup(&export::ExportEnvelope::new(crate::SCHEMA_VERSION, data), path_str)?;
+
+    {
+        let conn = db.lock()?;
+        crate::write_setting(&conn, LAST_BACKUP_KEY, &now.to_rfc3339()).map_err(|e| e.to_string())?;
+    }
+
+    prune_backups(&dir, settings.keep as usize)
+}
+
+// Only files this module named are considered, so other files in the folder are safe
+fn prune_backups(dir: &Path, keep: usize) -> Result<(), String> {
+    let mut backups: Vec<PathBuf> = fs::read_dir(dir)
+        .map_err(|e| format!("Cannot read '{}': {}", dir.display(), e))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with(FILE_PREFIX) && name.ends_with(FILE_SUFFIX))
+        })
+        .collect();
+
+    backups.sort();
+    let excess = backups.len().saturating_sub(keep);
+    for path in backups.into_iter().take(excess) {
+        if let Err(err) = fs::remove_file(&path) {
+            eprintln!("failed to remove old backup '{}': {}", path.display(), err);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use rusqlite::{Connection, DatabaseName, ErrorCode};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+// Returned by every command while the database is waiting for its passphrase
+pub const LOCKED_ERROR: &str = "Locked: enter your passphrase to unlock ZenTrack";
+
+// Emitted when the app locks after it has started, so the unlock screen can come back
+pub const LOCKED_EVENT: &str = "database-locked";
+
+// The encrypted file waiting to be unlocked; None while the database is open
+static LOCKED_PATH: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+// Whether the open database was opened with a key
+static ENCRYPTED: AtomicBool = AtomicBool::new(false);
+
+pub fn is_locked() -> bool {
+    LOCKED_PATH.lock().map(|path| path.is_some()).unwrap_or(false)
+}
+
+pub fn locked_path() -> Option<PathBuf> {
+    LOCKED_PATH.lock().ok().and_then(|path| path.clone())
+}
+
+pub fn set_locked(path: Option<PathBuf>) {
+    if let Ok(mut locked) = LOCKED_PATH.lock() {
+        *locked = path;
+    }
+}
+
+pub fn is_encrypted() -> bool {
+    ENCRYPTED.load(Ordering::Relaxed)
+}
+
+pub fn set_encrypted(encrypted: bool) {
+    ENCRYPTED.store(encrypted, Ordering::Relaxed);
+}
+
+// What SQLCipher reports for an encrypted file opened without its key, or with the wrong one
+pub fn is_not_a_database(err: &rusqlite::Error) -> bool {
+    err.sqlite_error_code() == Some(ErrorCode::NotADatabase)
+}
+
+// Copies the open database into a sibling file encrypted with `passphrase` (an empty one writes
+// plain SQLite) and returns its path. SQLCipher can only rekey files that are already
+// encrypted, so this goes through sqlcipher_export, which handles every direction.
+// The passphrase itself is never stored; SQLCipher keeps only its per-file key salt.
+pub fn export_rekeyed(conn: &Connection, db_path: &Path, passphrase: &str) -> Result<PathBuf, String> {
+    let mut rekeyed_path = db_path.as_os_str().to_owned();
+    rekeyed_path.push(".rekey");
+    let rekeyed_path = PathBuf::from(rekeyed_path);
+    if rekeyed_path.exists() {
+        fs::remove_file(&rekeyed_path).map_err(|e| e.to_string())?;
+    }
+
+    let rekeyed_str = rekeyed_path.to_str().ok_or_else(|| "Database path is not valid UTF-8".to_string())?;
+    conn.execute(
+        "ATTACH DATABASE ?1 AS rekeyed KEY ?2",
+        rusqlite::params![rekeyed_str, passphrase],
+    )
+    .map_err(|e| e.to_string())?;
+
+    let exported = conn
+        .query_row("SELECT sqlcipher_export('rekeyed')", [], |_| Ok(()))
+        .and_then(|_| {
+            // sqlcipher_export copies the schema and rows but not the schema version
+            let version: i64 = conn.query_row("PRAGMA main.user_version", [], |row| row.get(0))?;
+            conn.pragma_update(Some(DatabaseName::Attached("rekeyed")), "user_version", version)
+        });
+    let detached = conn.execute("DETACH DATABASE rekeyed", []);
+
+    exported.map_err(|e| e.to_string())?;
+    detached.map_err(|e| e.to_string())?;
+
+    Ok(rekeyed_path)
+}
+
+// Moves the rekeyed copy over the original. The original connection must be closed first, and
+// any WAL files left beside it are removed so they aren't replayed into the new file.
+pub fn replace_database_file(rekeyed_path: &Path, db_path: &Path) -> Result<(), String> {
+    for suffix in ["-wal", "-shm"] {
+        let mut side_file = db_path.as_os_str().to_owned();
+        side_file.push(suffix);
+        let side_file = PathBuf::from(side_file);
+        if side_file.exists() {
+            fs::remove_file(&side_file).map_err(|e| e.to_string())?;
+        }
+    }
+
+    fs::rename(rekeyed_path, db_path)
+        .map_err(|e| format!("Cannot replace '{}': {}", db_path.display(), e))
+}
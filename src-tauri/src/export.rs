@@ -0,0 +1,141 @@
+use chrono::{SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+use crate::reminders::{Reminder, ScheduledReminder};
+use crate::{AppSetting, CategoryBudget, CategoryRule, Exam, Expense, Subject, Task, TaskHistoryEntry, TaskNote, TimeEntry};
+
+const TASK_CSV_HEADER: &str = "id,title,description,due_date,tags,priority,status,subject_id,estimated_minutes,actual_minutes,is_pinned,created_at,status_changed_at,color";
+
+pub fn write_tasks(tasks: &[Task], format: &str, path: &str) -> Result<(), String> {
+    let contents = match format {
+        "json" => serde_json::to_string_pretty(tasks).map_err(|e| e.to_string())?,
+        "csv" => tasks_to_csv(tasks),
+        other => return Err(format!("Unsupported export format '{}', expected json or csv", other)),
+    };
+
+    fs::write(path, contents).map_err(|e| format!("Cannot write '{}': {}", path, e))
+}
+
+fn tasks_to_csv(tasks: &[Task]) -> String {
+    let optional = |value: Option<i64>| value.map(|v| v.to_string()).unwrap_or_default();
+
+    let mut lines = vec![TASK_CSV_HEADER.to_string()];
+    for task in tasks {
+        let fields = [
+            task.id.to_string(),
+            csv_field(&task.title),
+            csv_field(&task.description),
+            csv_field(task.due_date.as_deref().unwrap_or_default()),
+            csv_field(&task.tags.join(";")),
+            csv_field(&task.priority),
+            csv_field(&task.status),
+            optional(task.subject_id),
+            optional(task.estimated_minutes),
+            optional(task.actual_minutes),
+            task.is_pinned.to_string(),
+            csv_field(task.created_at.as_deref().unwrap_or_default()),
+            csv_field(task.status_changed_at.as_deref().unwrap_or_default()),
+            csv_field(task.color.as_deref().unwrap_or_default()),
+        ];
+        lines.push(fields.join(","));
+    }
+
+    lines.join("\n") + "\n"
+}
+
+// Quote fields containing separators, quotes or newlines (RFC 4180)
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+// Wrapper around a full backup, so an import knows which app and schema produced it
+#[derive(Serialize, Deserialize)]
+pub struct ExportEnvelope {
+    pub zentrack_version: String,
+    pub schema_version: i64,
+    pub exported_at: String,
+    pub data: ExportData,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct ExportData {
+    #[serde(default)]
+    pub tasks: Vec<Task>,
+    #[serde(default)]
+    pub time_entries: Vec<TimeEntry>,
+    #[serde(default)]
+    pub expenses: Vec<Expense>,
+    #[serde(default)]
+    pub category_rules: Vec<CategoryRule>,
+    #[serde(default)]
+    pub subjects: Vec<Subject>,
+    #[serde(default)]
+    pub exams: Vec<Exam>,
+    #[serde(default)]
+    pub reminders: Vec<Reminder>,
+    #[serde(default)]
+    pub task_notes: Vec<TaskNote>,
+    #[serde(default)]
+    pub task_history: Vec<TaskHistoryEntry>,
+    #[serde(default)]
+    pub category_budgets: Vec<CategoryBudget>,
+    #[serde(default)]
+    pub app_settings: Vec<AppSetting>,
+}
+
+impl ExportEnvelope {
+    pub fn new(schema_version: i64, data: ExportData) -> Self {
+        ExportEnvelope {
+            zentrack_version: env!("CARGO_PKG_VERSION").to_string(),
+            schema_version,
+            exported_at: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+            data,
+        }
+    }
+}
+
+pub fn write_backup(envelope: &ExportEnvelope, path: &str) -> Result<(), String> {
+    let contents = serde_json::to_string_pretty(envelope).map_err(|e| e.to_string())?;
+    fs::write(path, contents).map_err(|e| format!("Cannot write '{}': {}", path, e))
+}
+
+// Older schemas are accepted (missing sections default to empty); newer ones are refused
+pub fn read_backup(path: &str, supported_schema: i64) -> Result<ExportEnvelope, String> {
+    let contents = fs::read_to_string(path).map_err(|e| format!("Cannot read '{}': {}", path, e))?;
+    let value: serde_json::Value =
+        serde_json::from_str(&contents).map_err(|e| format!("'{}' is not valid JSON: {}", path, e))?;
+
+    // Check the version before the data, whose shape may differ in newer schemas
+    let schema_version = value
+        .get("schema_version")
+        .and_then(|version| version.as_i64())
+        .ok_or_else(|| format!("'{}' is not a ZenTrack export (no schema_version)", path))?;
+
+    if schema_version > supported_schema {
+        let exported_by = value
+            .get("zentrack_version")
+            .and_then(|version| version.as_str())
+            .unwrap_or("a newer version");
+        return Err(format!(
+            "This backup was made by ZenTrack {} with schema version {}, but this version only understands up to {}. Please update ZenTrack to import it.",
+            exported_by, schema_version, supported_schema
+        ));
+    }
+
+    serde_json::from_value(value).map_err(|e| format!("Backup data is malformed: {}", e))
+}
+
+pub fn write_reminders(reminders: &[ScheduledReminder], path: &str) -> Result<(), String> {
+    let contents = serde_json::to_string_pretty(reminders).map_err(|e| e.to_string())?;
+    fs::write(path, contents).map_err(|e| format!("Cannot write '{}': {}", path, e))
+}
+
+pub fn read_reminders(path: &str) -> Result<Vec<ScheduledReminder>, String> {
+    let contents = fs::read_to_string(path).map_err(|e| format!("Cannot read '{}': {}", path, e))?;
+    serde_json::from_str(&contents).map_err(|e| format!("'{}' is not a reminder export: {}", path, e))
+}
@@ -0,0 +1,138 @@
+use chrono::{Local, NaiveDate, SecondsFormat};
+use serde::{Deserialize, Serialize};
+
+use crate::reminders::{normalize_datetime, resolve_local_datetime};
+use crate::Expense;
+
+// Names the header columns holding each field, so different bank formats can be imported
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ColumnMapping {
+    pub amount: String,
+    pub description: String,
+    pub date: String,
+    // chrono format for date-only values, e.g. "%d/%m/%Y"; ISO dates and datetimes always work
+    #[serde(default)]
+    pub date_format: Option<String>,
+    // Banks that export spending as positive amounts set this to false
+    #[serde(default = "default_negative_is_expense")]
+    pub negative_is_expense: bool,
+}
+
+fn default_negative_is_expense() -> bool {
+    true
+}
+
+// Parses a bank export into uncategorized expenses; errors name the offending line
+pub fn parse_expenses_csv(contents: &str, mapping: &ColumnMapping) -> Result<Vec<Expense>, String> {
+    let mut records = parse_csv_records(contents)?.into_iter();
+
+    let (_, header) = records.next().ok_or_else(|| "CSV file is empty".to_string())?;
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|field| field.trim().eq_ignore_ascii_case(name.trim()))
+            .ok_or_else(|| format!("Column '{}' not found in CSV header", name))
+    };
+    let amount_col = column(&mapping.amount)?;
+    let description_col = column(&mapping.description)?;
+    let date_col = column(&mapping.date)?;
+
+    let mut expenses = Vec::new();
+    for (line, fields) in records {
+        let field = |index: usize| {
+            fields
+                .get(index)
+                .map(|value| value.trim())
+                .ok_or_else(|| format!("Line {}: missing column {}", line, index + 1))
+        };
+
+        let raw_amount = field(amount_col)?;
+        let amount: f64 = raw_amount
+            .parse()
+            .map_err(|_| format!("Line {}: invalid amount '{}'", line, raw_amount))?;
+
+        let raw_date = field(date_col)?;
+        let date = parse_import_date(raw_date, mapping.date_format.as_deref())
+            .ok_or_else(|| format!("Line {}: invalid date '{}'", line, raw_date))?;
+
+        let is_expense = (amount < 0.0) == mapping.negative_is_expense;
+
+        expenses.push(Expense {
+            id: 0,
+            amount: amount.abs(),
+            description: field(description_col)?.to_string(),
+            category: String::new(),
+            date,
+            expense_type: if is_expense { "expense" } else { "income" }.to_string(),
+        });
+    }
+
+    Ok(expenses)
+}
+
+// Stored like the frontend's toISOString(); date-only values are taken as local midnight
+fn parse_import_date(raw: &str, date_format: Option<&str>) -> Option<String> {
+    let instant = normalize_datetime(raw).or_else(|| {
+        let date = NaiveDate::parse_from_str(raw, date_format.unwrap_or("%Y-%m-%d")).ok()?;
+        resolve_local_datetime(&Local, date.and_hms_opt(0, 0, 0)?)
+    })?;
+
+    Some(instant.to_rfc3339_opts(SecondsFormat::Millis, true))
+}
+
+// Records with the line number they start on; quoted fields may contain commas, quotes and newlines
+fn parse_csv_records(contents: &str) -> Result<Vec<(usize, Vec<String>)>, String> {
+    let mut records = Vec::new();
+    let mut fields: Vec<String> = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut record_line = 1;
+
+    let mut chars = contents.trim_start_matches('\u{feff}').chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                '\n' => {
+                    line += 1;
+                    field.push(c);
+                }
+                _ => field.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            '"' => in_quotes = true,
+            ',' => fields.push(std::mem::take(&mut field)),
+            '\r' => {}
+            '\n' => {
+                fields.push(std::mem::take(&mut field));
+                // Blank lines are skipped rather than treated as empty rows
+                if fields.iter().any(|value| !value.trim().is_empty()) {
+                    records.push((record_line, std::mem::take(&mut fields)));
+                }
+                fields.clear();
+                line += 1;
+                record_line = line;
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(format!("Line {}: unterminated quoted field", record_line));
+    }
+
+    fields.push(field);
+    if fields.iter().any(|value| !value.trim().is_empty()) {
+        records.push((record_line, fields));
+    }
+
+    Ok(records)
+}
@@ -3,12 +3,18 @@
 
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
-use std::sync::{Arc, Mutex};
-use tauri::{State, Manager};
-use rusqlite::{Connection, OptionalExtension, Result as SqliteResult};
-use chrono::{Local, NaiveDateTime, NaiveDate, TimeZone, Datelike};
+use std::sync::{Arc, Mutex, MutexGuard};
+use tauri::{AppHandle, Emitter, State, Manager};
+use tauri_plugin_notification::NotificationExt;
+use rusqlite::{types::Value, Connection, OptionalExtension, Result as SqliteResult};
+use chrono::{DateTime, Local, NaiveDateTime, NaiveDate, SecondsFormat, TimeZone, Datelike, Utc};
 
+mod backup;
+mod encryption;
+mod export;
+mod import;
 mod reminders;
+mod report;
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Task {
@@ -25,6 +31,75 @@ pub struct Task {
     pub estimated_minutes: Option<i64>,
     #[serde(default)]
     pub actual_minutes: Option<i64>,
+    #[serde(default)]
+    pub is_pinned: bool,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub status_changed_at: Option<String>,
+    #[serde(default)]
+    pub completed_at: Option<String>,
+    #[serde(default)]
+    pub color: Option<String>, // "#RRGGBB", used to tint the task's card
+}
+
+// All fields optional; an empty filter matches every task
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct TaskFilter {
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub priority: Option<String>,
+    #[serde(default)]
+    pub tag: Option<String>,
+    #[serde(default)]
+    pub due_from: Option<String>,
+    #[serde(default)]
+    pub due_to: Option<String>,
+    // Case-insensitive substring of the title or description
+    #[serde(default)]
+    pub text: Option<String>,
+    // Exclusive bounds, as a date or local date-time
+    #[serde(default)]
+    pub due_before: Option<String>,
+    #[serde(default)]
+    pub due_after: Option<String>,
+    // Any key task_sort_expression knows; only query_tasks reads these two
+    #[serde(default)]
+    pub sort_by: Option<String>,
+    #[serde(default)]
+    pub desc: bool,
+    #[serde(default)]
+    pub limit: Option<u32>,
+    #[serde(default)]
+    pub offset: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TaskHistoryEntry {
+    pub id: i64,
+    pub task_id: i64,
+    pub field: String,
+    pub old_value: String,
+    pub new_value: String,
+    pub changed_at: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TaskNote {
+    pub id: i64,
+    pub task_id: i64,
+    pub body: String,
+    pub created_at: String,
+}
+
+// Everything the task detail panel shows, loaded under one lock so the parts agree
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TaskDetail {
+    pub task: Task,
+    pub reminders: Vec<reminders::Reminder>,
+    pub notes: Vec<TaskNote>,
+    pub time_entries: Vec<TimeEntry>, // linked through task_id, archived ones included
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -77,6 +152,42 @@ pub struct TimeEntry {
     pub category: String,
     #[serde(default)]
     pub subject_id: Option<i64>,
+    #[serde(default)]
+    pub task_id: Option<i64>,
+    #[serde(default)]
+    pub archived: bool,
+    // Free-text note on what was done; empty when not given
+    #[serde(default)]
+    pub description: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DayTimeLog {
+    pub date: String,
+    pub entries: Vec<TimeEntry>,
+    pub total_duration: i64, // seconds
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GoalProgress {
+    pub date: String,
+    pub tracked_seconds: i64,
+    pub goal_minutes: Option<u32>, // None when no daily goal is set
+    pub percent: Option<f64>,      // may exceed 100
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ClosedTimers {
+    pub closed: Vec<TimeEntry>,
+    pub skipped: Vec<TimeEntry>, // start is after the requested end time or unreadable; left open
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct EstimateComparison {
+    pub task_id: i64,
+    pub estimated_minutes: Option<i64>, // None when the task has no estimate
+    pub actual_minutes: Option<i64>,    // None when no finished time entry is linked
+    pub delta_minutes: Option<i64>,     // actual - estimated, when both are known
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -89,31 +200,184 @@ pub struct Expense {
     pub expense_type: String, // "expense" or "income"
 }
 
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CashflowPoint {
+    pub period: String,
+    pub income: f64,
+    pub expense: f64,
+    pub balance: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CategoryDetail {
+    pub category: String,
+    pub expenses: Vec<Expense>,
+    pub total: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CategoryBudget {
+    pub category: String,
+    pub monthly_limit: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AppSetting {
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct BudgetWarning {
+    pub category: String,
+    pub monthly_limit: f64,
+    pub spent: f64,
+    pub overage: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CategoryStat {
+    pub category: String,
+    pub count: u64,
+    pub total: f64,
+    pub average: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CategoryDelta {
+    pub category: String,
+    pub this_month: f64,
+    pub last_month: f64,
+    pub difference: f64,             // this_month - last_month
+    pub percent_change: Option<f64>, // None when nothing was spent last month
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AddExpenseResult {
+    pub expenses: Vec<Expense>,
+    pub budget_warning: Option<BudgetWarning>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CategoryRule {
+    pub id: i64,
+    pub keyword: String,
+    pub category: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DbStats {
+    pub task_count: i64,
+    pub time_entry_count: i64,
+    pub expense_count: i64,
+    pub reminder_count: i64,
+    pub file_size_bytes: u64,
+    pub schema_version: i64,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SelfTestCheck {
+    pub name: String,
+    pub passed: bool,
+    pub detail: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SelfTestReport {
+    pub passed: bool,
+    pub checks: Vec<SelfTestCheck>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DataIssue {
+    pub table: String,
+    pub row_id: i64,
+    pub problem: String,
+}
+
 #[derive(Clone)]
 struct DatabaseConnection(Arc<Mutex<Connection>>);
 
-fn get_db_path() -> PathBuf {
-    // Use app data directory for better cross-platform support
-    let mut path = dirs::data_local_dir()
-        .unwrap_or_else(|| std::env::current_dir().unwrap());
-    path.push("zentrack");
+impl DatabaseConnection {
+    // Commands go through here so an encrypted database that isn't unlocked yet gives one
+    // recognizable error instead of whatever SQLite reports
+    fn lock(&self) -> Result<MutexGuard<'_, Connection>, String> {
+        if encryption::is_locked() {
+            return Err(encryption::LOCKED_ERROR.to_string());
+        }
+        self.0.lock().map_err(|e| e.to_string())
+    }
+}
+
+// Stored in PRAGMA user_version; bump when the schema changes
+const SCHEMA_VERSION: i64 = 16;
+
+// SQL condition for open tasks whose due date has passed (due dates are stored as local "%Y-%m-%dT%H:%M",
+// or NULL when unset; the empty-string check guards rows written before that was normalized)
+const OVERDUE_TASK_FILTER: &str = "status != 'Done' AND due_date IS NOT NULL AND due_date != '' \
+     AND due_date < strftime('%Y-%m-%dT%H:%M', 'now', 'localtime')";
+
+// Overrides the data directory, e.g. for portable installs or test runs
+const DATA_DIR_ENV: &str = "ZENTRACK_DATA_DIR";
+
+fn get_db_path() -> Result<PathBuf, String> {
+    // The env override wins over the app data directory
+    let env_dir = std::env::var_os(DATA_DIR_ENV)
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from);
+
+    let mut path = match env_dir {
+        Some(dir) => dir,
+        None => {
+            // Use app data directory for better cross-platform support
+            let mut default_dir = match dirs::data_local_dir() {
+                Some(dir) => dir,
+                None => std::env::current_dir()
+                    .map_err(|e| format!("Cannot resolve a data directory: {}", e))?,
+            };
+            default_dir.push("zentrack");
+            default_dir
+        }
+    };
+
     // Create directory if it doesn't exist
-    std::fs::create_dir_all(&path).ok();
+    std::fs::create_dir_all(&path)
+        .map_err(|e| format!("Cannot create data directory '{}': {}", path.display(), e))?;
     path.push("zentrack.db");
-    path
+    Ok(path)
 }
 
-fn init_database() -> SqliteResult<Connection> {
-    let db_path = get_db_path();
-    
-    // Create parent directory if it doesn't exist
-    if let Some(parent) = db_path.parent() {
-        std::fs::create_dir_all(parent).ok();
-    }
-    
+// How long a connection waits on a lock held by another before failing with SQLITE_BUSY
+const BUSY_TIMEOUT_MS: u64 = 5000;
+
+// Every connection must be configured through here: these pragmas are per-connection in
+// SQLite, and without foreign_keys deleting a task would leave its reminders and history behind
+fn configure_connection(conn: &Connection) -> SqliteResult<()> {
+    conn.pragma_update(None, "foreign_keys", "ON")?;
+    // WAL lets readers work alongside a writer; it is persistent, but setting it again is cheap
+    conn.pragma_update(None, "journal_mode", "WAL")?;
+    conn.busy_timeout(std::time::Duration::from_millis(BUSY_TIMEOUT_MS))?;
+    Ok(())
+}
+
+// `passphrase` is only needed for an encrypted database; SQLCipher requires the key before
+// anything else touches the file
+fn open_connection(db_path: PathBuf, passphrase: Option<&str>) -> SqliteResult<Connection> {
     let conn = Connection::open(db_path)?;
+    if let Some(passphrase) = passphrase {
+        conn.pragma_update(None, "key", passphrase)?;
+    }
+    configure_connection(&conn)?;
+    Ok(conn)
+}
+
+fn init_database(db_path: PathBuf, passphrase: Option<&str>) -> SqliteResult<Connection> {
+    let conn = open_connection(db_path, passphrase)?;
 
-    conn.execute("PRAGMA foreign_keys = ON;", [])?;
+    // Schema version the file was last opened with, for one-time data migrations (0 for new files)
+    let stored_version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
     
     // Create tasks table if it doesn't exist
     conn.execute(
@@ -129,6 +393,32 @@ fn init_database() -> SqliteResult<Connection> {
         [],
     )?;
 
+    // Create task_history table as an audit log of status/priority/due date changes
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS task_history (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            task_id INTEGER NOT NULL,
+            field TEXT NOT NULL,
+            old_value TEXT NOT NULL,
+            new_value TEXT NOT NULL,
+            changed_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+            FOREIGN KEY(task_id) REFERENCES tasks(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    // Create task_notes table for timestamped progress notes on a task
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS task_notes (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            task_id INTEGER NOT NULL,
+            body TEXT NOT NULL,
+            created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+            FOREIGN KEY(task_id) REFERENCES tasks(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
     // Create time_entries table if it doesn't exist
     conn.execute(
         "CREATE TABLE IF NOT EXISTS time_entries (
@@ -157,6 +447,27 @@ fn init_database() -> SqliteResult<Connection> {
         [],
     )?;
 
+    // Create category_rules table for keyword-based expense categorization
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS category_rules (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            keyword TEXT NOT NULL,
+            category TEXT NOT NULL,
+            created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+        )",
+        [],
+    )?;
+
+    // Create category_budgets table for per-category monthly spending limits
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS category_budgets (
+            category TEXT PRIMARY KEY,
+            monthly_limit REAL NOT NULL,
+            updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+        )",
+        [],
+    )?;
+
     // Create subjects table for student-specific tracking
     conn.execute(
         "CREATE TABLE IF NOT EXISTS subjects (
@@ -189,9 +500,33 @@ fn init_database() -> SqliteResult<Connection> {
     let _ = conn.execute("ALTER TABLE tasks ADD COLUMN subject_id INTEGER", []);
     let _ = conn.execute("ALTER TABLE tasks ADD COLUMN estimated_minutes INTEGER DEFAULT 60", []);
     let _ = conn.execute("ALTER TABLE tasks ADD COLUMN actual_minutes INTEGER DEFAULT 0", []);
+    let _ = conn.execute("ALTER TABLE tasks ADD COLUMN completed_at TEXT", []);
+    let _ = conn.execute("ALTER TABLE tasks ADD COLUMN is_pinned INTEGER NOT NULL DEFAULT 0", []);
+    // SQLite can't add a column with a CURRENT_TIMESTAMP default, so inserts set it explicitly.
+    // Older rows are backfilled with their completion time, or the migration time when open.
+    let _ = conn.execute("ALTER TABLE tasks ADD COLUMN created_at DATETIME", []);
+    // One-time: missing due dates used to be written as "", they are NULL from version 8 on
+    if stored_version < 8 {
+        conn.execute("UPDATE tasks SET due_date = NULL WHERE TRIM(due_date) = ''", [])?;
+    }
+    conn.execute(
+        "UPDATE tasks SET created_at = COALESCE(completed_at, CURRENT_TIMESTAMP) WHERE created_at IS NULL",
+        [],
+    )?;
+    // When the status last changed; older rows start from their creation time
+    let _ = conn.execute("ALTER TABLE tasks ADD COLUMN status_changed_at DATETIME", []);
+    let _ = conn.execute("ALTER TABLE tasks ADD COLUMN color TEXT", []);
+    conn.execute(
+        "UPDATE tasks SET status_changed_at = COALESCE(created_at, CURRENT_TIMESTAMP) WHERE status_changed_at IS NULL",
+        [],
+    )?;
     
     // Add subject_id to time_entries for subject-based tracking
     let _ = conn.execute("ALTER TABLE time_entries ADD COLUMN subject_id INTEGER", []);
+    let _ = conn.execute("ALTER TABLE time_entries ADD COLUMN task_id INTEGER REFERENCES tasks(id) ON DELETE SET NULL", []);
+    // Archived entries are kept for history but hidden from the default listing
+    let _ = conn.execute("ALTER TABLE time_entries ADD COLUMN archived INTEGER NOT NULL DEFAULT 0", []);
+    let _ = conn.execute("ALTER TABLE time_entries ADD COLUMN description TEXT NOT NULL DEFAULT ''", []);
 
     // Create study_streaks table for guilt-free streaks
     conn.execute(
@@ -224,83 +559,229 @@ fn init_database() -> SqliteResult<Connection> {
     )?;
 
     reminders::init_reminders_table(&conn)?;
-    
+
+    conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+
+    // Every caller makes the returned connection the active one
+    encryption::set_encrypted(passphrase.is_some());
     Ok(conn)
 }
 
+const TASK_COLUMNS: &str = "id, title, description, due_date, tags, priority, status, subject_id, estimated_minutes, actual_minutes, is_pinned, created_at, status_changed_at, color, completed_at";
+
+fn task_from_row(row: &rusqlite::Row) -> SqliteResult<Task> {
+    let tags_str: String = row.get(4)?;
+    let tags: Vec<String> = serde_json::from_str(&tags_str).unwrap_or_default();
+
+    Ok(Task {
+        id: row.get(0)?,
+        title: row.get(1)?,
+        description: row.get(2)?,
+        due_date: row.get(3)?,
+        tags,
+        priority: row.get(5)?,
+        status: row.get(6)?,
+        subject_id: row.get(7)?,
+        estimated_minutes: row.get(8)?,
+        actual_minutes: row.get(9)?,
+        is_pinned: row.get(10)?,
+        created_at: row.get(11)?,
+        status_changed_at: row.get(12)?,
+        completed_at: row.get(14)?,
+        color: row.get(13)?,
+    })
+}
+
+fn load_task(conn: &Connection, id: i64) -> SqliteResult<Option<Task>> {
+    conn.query_row(
+        &format!("SELECT {} FROM tasks WHERE id = ?1", TASK_COLUMNS),
+        rusqlite::params![id],
+        task_from_row,
+    )
+    .optional()
+}
+
 fn load_tasks(conn: &Connection) -> SqliteResult<Vec<Task>> {
-    let mut stmt = conn.prepare("SELECT id, title, description, due_date, tags, priority, status, subject_id, estimated_minutes, actual_minutes FROM tasks")?;
-    
-    let tasks = stmt.query_map([], |row| {
-        let tags_str: String = row.get(4)?;
-        let tags: Vec<String> = serde_json::from_str(&tags_str).unwrap_or_default();
-        
-        Ok(Task {
-            id: row.get(0)?,
-            title: row.get(1)?,
-            description: row.get(2)?,
-            due_date: row.get(3)?,
-            tags,
-            priority: row.get(5)?,
-            status: row.get(6)?,
-            subject_id: row.get(7)?,
-            estimated_minutes: row.get(8)?,
-            actual_minutes: row.get(9)?,
-        })
-    })?;
-    
+    load_tasks_filtered(conn, &TaskFilter::default())
+}
+
+// Builds the WHERE clause shared by the filtered getter and the exporters
+fn build_task_filter(filter: &TaskFilter) -> (String, Vec<Value>) {
+    let mut conditions: Vec<&str> = Vec::new();
+    let mut values: Vec<Value> = Vec::new();
+
+    if let Some(ref status) = filter.status {
+        conditions.push("status = ?");
+        values.push(Value::Text(status.clone()));
+    }
+    if let Some(ref priority) = filter.priority {
+        conditions.push("priority = ?");
+        values.push(Value::Text(priority.clone()));
+    }
+    if let Some(ref tag) = filter.tag {
+        // Case-insensitive, since stored tags may have been lowercased
+        conditions.push("EXISTS (SELECT 1 FROM json_each(tasks.tags) WHERE lower(json_each.value) = lower(?))");
+        values.push(Value::Text(tag.clone()));
+    }
+    // Date bounds are inclusive days; tasks without a due date never match a range
+    if let Some(ref due_from) = filter.due_from {
+        conditions.push("date(due_date) >= date(?)");
+        values.push(Value::Text(due_from.clone()));
+    }
+    if let Some(ref due_to) = filter.due_to {
+        conditions.push("date(due_date) <= date(?)");
+        values.push(Value::Text(due_to.clone()));
+    }
+    if let Some(ref due_before) = filter.due_before {
+        conditions.push("datetime(due_date) < datetime(?)");
+        values.push(Value::Text(due_before.clone()));
+    }
+    if let Some(ref due_after) = filter.due_after {
+        conditions.push("datetime(due_date) > datetime(?)");
+        values.push(Value::Text(due_after.clone()));
+    }
+    if let Some(text) = filter.text.as_deref().map(str::trim).filter(|text| !text.is_empty()) {
+        // LIKE wildcards in the search text match literally
+        let pattern = format!("%{}%", text.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_"));
+        conditions.push("(title LIKE ? ESCAPE '\\' OR description LIKE ? ESCAPE '\\')");
+        values.push(Value::Text(pattern.clone()));
+        values.push(Value::Text(pattern));
+    }
+
+    if conditions.is_empty() {
+        (String::new(), values)
+    } else {
+        (format!(" WHERE {}", conditions.join(" AND ")), values)
+    }
+}
+
+fn load_tasks_filtered(conn: &Connection, filter: &TaskFilter) -> SqliteResult<Vec<Task>> {
+    load_tasks_ordered(conn, filter, "id")
+}
+
+// `order_by` is trusted SQL from a fixed mapping, never user input
+fn load_tasks_ordered(conn: &Connection, filter: &TaskFilter, order_by: &str) -> SqliteResult<Vec<Task>> {
+    let (where_clause, mut values) = build_task_filter(filter);
+
+    // A negative LIMIT means no limit, so an offset works on its own
+    let page_clause = if filter.limit.is_some() || filter.offset.is_some() {
+        values.push(Value::Integer(filter.limit.map(i64::from).unwrap_or(-1)));
+        values.push(Value::Integer(filter.offset.map(i64::from).unwrap_or(0)));
+        " LIMIT ? OFFSET ?"
+    } else {
+        ""
+    };
+
+    // Pinned tasks always come first
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM tasks{} ORDER BY is_pinned DESC, {}{}",
+        TASK_COLUMNS, where_clause, order_by, page_clause
+    ))?;
+
+    let tasks = stmt.query_map(rusqlite::params_from_iter(values), task_from_row)?;
+
     tasks.collect()
 }
 
+// Maps a sort key from the frontend to its ORDER BY expression. Priority and status sort by
+// meaning (Low < Medium < High, Pending < In Progress < Done) rather than alphabetically.
+fn task_sort_expression(by: &str) -> Option<&'static str> {
+    match by {
+        "title" => Some("title COLLATE NOCASE"),
+        "due_date" => Some("NULLIF(due_date, '')"),
+        "priority" => Some("CASE priority WHEN 'Low' THEN 1 WHEN 'Medium' THEN 2 WHEN 'High' THEN 3 ELSE 0 END"),
+        "status" => Some("CASE status WHEN 'Pending' THEN 1 WHEN 'In Progress' THEN 2 WHEN 'Done' THEN 3 ELSE 0 END"),
+        "created_at" => Some("created_at"),
+        _ => None,
+    }
+}
+
 #[tauri::command]
 fn get_tasks(state: State<'_, DatabaseConnection>) -> Result<Vec<Task>, String> {
-    let conn = state.0.lock().map_err(|e| e.to_string())?;
+    let conn = state.lock()?;
     load_tasks(&conn).map_err(|e| e.to_string())
 }
 
-#[tauri::command]
-fn add_task(state: State<'_, DatabaseConnection>, task: Task) -> Result<Vec<Task>, String> {
-    let conn = state.0.lock().map_err(|e| e.to_string())?;
+// Tag limits can be overridden through app settings
+const MAX_TAGS_KEY: &str = "max_tags_per_task";
+const MAX_TAG_LENGTH_KEY: &str = "max_tag_length";
+const DEFAULT_MAX_TAGS: usize = 20;
+const DEFAULT_MAX_TAG_LENGTH: usize = 50;
+// Set to "false" to keep the casing of a tag's first appearance instead of lowercasing
+const LOWERCASE_TAGS_KEY: &str = "lowercase_tags";
 
-    let tags_json = serde_json::to_string(&task.tags).map_err(|e| e.to_string())?;
-    let due_date = task.due_date.clone().unwrap_or_default();
+// Trims, case-folds and dedupes tags (keeping first-appearance order), then enforces the limits
+fn validate_tags(conn: &Connection, tags: &[String]) -> Result<Vec<String>, String> {
+    let limit = |key: &str, default: usize| -> Result<usize, String> {
+        Ok(read_setting(conn, key)
+            .map_err(|e| e.to_string())?
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(default))
+    };
+    let max_tags = limit(MAX_TAGS_KEY, DEFAULT_MAX_TAGS)?;
+    let max_length = limit(MAX_TAG_LENGTH_KEY, DEFAULT_MAX_TAG_LENGTH)?;
+    let lowercase = read_setting(conn, LOWERCASE_TAGS_KEY)
+        .map_err(|e| e.to_string())?
+        .map(|value| value != "false")
+        .unwrap_or(true);
 
-    conn.execute(
-        "INSERT INTO tasks (title, description, due_date, tags, priority, status, subject_id, estimated_minutes, actual_minutes) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
-        rusqlite::params![
-            &task.title,
-            &task.description,
-            &due_date,
-            &tags_json,
-            &task.priority,
-            &task.status,
-            task.subject_id,
-            task.estimated_minutes.unwrap_or(60),
-            task.actual_minutes.unwrap_or(0)
-        ],
-    ).map_err(|e| e.to_string())?;
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.trim();
+        if tag.is_empty() {
+            return Err("Tags cannot be empty".to_string());
+        }
+        if tag.chars().count() > max_length {
+            return Err(format!("Tag '{}' is longer than {} characters", tag, max_length));
+        }
 
-    load_tasks(&conn).map_err(|e| e.to_string())
+        let tag = if lowercase { tag.to_lowercase() } else { tag.to_string() };
+        if !normalized.iter().any(|existing| existing.to_lowercase() == tag.to_lowercase()) {
+            normalized.push(tag);
+        }
+    }
+
+    if normalized.len() > max_tags {
+        return Err(format!("A task can have at most {} tags ({} given)", max_tags, normalized.len()));
+    }
+
+    Ok(normalized)
 }
 
-#[tauri::command]
-fn update_task(state: State<'_, DatabaseConnection>, task: Task) -> Result<Vec<Task>, String> {
-    let conn = state.0.lock().map_err(|e| e.to_string())?;
+// A blank color is stored as NULL; anything else must be "#RRGGBB"
+fn validate_color(color: Option<&str>) -> Result<Option<String>, String> {
+    let color = match color.map(str::trim).filter(|color| !color.is_empty()) {
+        Some(color) => color,
+        None => return Ok(None),
+    };
 
-    let tags_json = serde_json::to_string(&task.tags).map_err(|e| e.to_string())?;
-    let due_date = task.due_date.clone().unwrap_or_default();
+    let is_hex = color.len() == 7
+        && color.starts_with('#')
+        && color[1..].chars().all(|c| c.is_ascii_hexdigit());
+    if !is_hex {
+        return Err(format!("Invalid color '{}', expected #RRGGBB", color));
+    }
 
-    let previous_due_date: Option<String> = conn
-        .query_row(
-            "SELECT due_date FROM tasks WHERE id = ?1",
-            rusqlite::params![task.id as i64],
-            |row| row.get(0),
-        )
-        .optional()
-        .map_err(|e| e.to_string())?;
+    Ok(Some(color.to_string()))
+}
+
+// A blank due date is stored as NULL
+fn stored_due_date(due_date: Option<&str>) -> Option<String> {
+    due_date
+        .map(str::trim)
+        .filter(|due_date| !due_date.is_empty())
+        .map(str::to_string)
+}
+
+fn insert_task(conn: &Connection, task: &Task) -> Result<i64, String> {
+    let tags = validate_tags(conn, &task.tags)?;
+    let tags_json = serde_json::to_string(&tags).map_err(|e| e.to_string())?;
+    let due_date = stored_due_date(task.due_date.as_deref());
+    let color = validate_color(task.color.as_deref())?;
 
     conn.execute(
-        "UPDATE tasks SET title = ?1, description = ?2, due_date = ?3, tags = ?4, priority = ?5, status = ?6, subject_id = ?7, estimated_minutes = ?8, actual_minutes = ?9 WHERE id = ?10",
+        "INSERT INTO tasks (title, description, due_date, tags, priority, status, subject_id, estimated_minutes, actual_minutes, is_pinned, color, completed_at, created_at, status_changed_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, CASE WHEN ?6 = 'Done' THEN datetime('now') END, datetime('now'), datetime('now'))",
         rusqlite::params![
             &task.title,
             &task.description,
@@ -311,365 +792,2581 @@ fn update_task(state: State<'_, DatabaseConnection>, task: Task) -> Result<Vec<T
             task.subject_id,
             task.estimated_minutes.unwrap_or(60),
             task.actual_minutes.unwrap_or(0),
-            task.id as i64
+            task.is_pinned,
+            &color
         ],
     ).map_err(|e| e.to_string())?;
 
-    reminders::recalculate_reminders_for_task(
-        &conn,
-        task.id as i64,
-        previous_due_date.as_deref(),
-        task.due_date.as_deref(),
-    )
-    .map_err(|e| e.to_string())?;
+    Ok(conn.last_insert_rowid())
+}
 
-    load_tasks(&conn).map_err(|e| e.to_string())
+#[tauri::command]
+fn get_tasks_filtered(state: State<'_, DatabaseConnection>, filter: TaskFilter) -> Result<Vec<Task>, String> {
+    let conn = state.lock()?;
+    load_tasks_filtered(&conn, &filter).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-fn delete_task(state: State<'_, DatabaseConnection>, id: u64) -> Result<Vec<Task>, String> {
-    let conn = state.0.lock().map_err(|e| e.to_string())?;
+fn get_tasks_without_due_date(state: State<'_, DatabaseConnection>) -> Result<Vec<Task>, String> {
+    let conn = state.lock()?;
 
-    conn.execute(
-        "DELETE FROM tasks WHERE id = ?1",
-        rusqlite::params![id as i64]
-    ).map_err(|e| e.to_string())?;
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM tasks WHERE due_date IS NULL OR TRIM(due_date) = '' ORDER BY is_pinned DESC, id",
+            TASK_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
 
-    load_tasks(&conn).map_err(|e| e.to_string())
-}
+    let tasks = stmt.query_map([], task_from_row).map_err(|e| e.to_string())?;
 
-#[tauri::command]
-fn create_reminder(state: State<'_, DatabaseConnection>, task_id: i64, remind_at: String) -> Result<(), String> {
-    let conn = state.0.lock().map_err(|e| e.to_string())?;
-    reminders::create_reminder(&conn, task_id, remind_at)
+    tasks.collect::<SqliteResult<Vec<_>>>().map_err(|e| e.to_string())
 }
 
+// Tasks due within [start, end], soonest first. Bounds are anything normalize_datetime accepts,
+// or a bare YYYY-MM-DD meaning the start or end of that local day. Older rows may hold due dates
+// in other formats, so each one is normalized in Rust before comparing; rows that don't parse
+// are left out.
 #[tauri::command]
-fn get_reminders_for_task(state: State<'_, DatabaseConnection>, task_id: i64) -> Result<Vec<reminders::Reminder>, String> {
-    let conn = state.0.lock().map_err(|e| e.to_string())?;
-    reminders::get_reminders_for_task(&conn, task_id)
+fn get_tasks_due_between(
+    state: State<'_, DatabaseConnection>,
+    start: String,
+    end: String,
+    include_done: bool,
+) -> Result<Vec<Task>, String> {
+    let parse_bound = |raw: &str, time: chrono::NaiveTime| {
+        reminders::normalize_datetime(raw)
+            .or_else(|| {
+                NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+                    .ok()
+                    .and_then(|date| reminders::resolve_local_datetime(&Local, date.and_time(time)))
+            })
+            .ok_or_else(|| format!("Invalid date or time '{}'", raw))
+    };
+    let start_dt = parse_bound(&start, chrono::NaiveTime::MIN)?;
+    let end_dt = parse_bound(&end, chrono::NaiveTime::from_hms_opt(23, 59, 59).unwrap_or_default())?;
+    if end_dt < start_dt {
+        return Err("End must not be before start".to_string());
+    }
+
+    let conn = state.lock()?;
+    load_tasks_due_where(&conn, include_done, |due| due >= start_dt && due <= end_dt).map_err(|e| e.to_string())
 }
 
-#[tauri::command]
-fn delete_reminder(state: State<'_, DatabaseConnection>, reminder_id: i64) -> Result<(), String> {
-    let conn = state.0.lock().map_err(|e| e.to_string())?;
-    reminders::delete_reminder(&conn, reminder_id)
+// Tasks whose normalized due date passes `in_range`, soonest first. Due dates that don't parse
+// are left out.
+fn load_tasks_due_where(
+    conn: &Connection,
+    include_done: bool,
+    in_range: impl Fn(DateTime<Utc>) -> bool,
+) -> SqliteResult<Vec<Task>> {
+    let mut tasks: Vec<(DateTime<Utc>, Task)> = load_tasks(conn)?
+        .into_iter()
+        .filter(|task| include_done || task.status != "Done")
+        .filter_map(|task| {
+            let due = task.due_date.as_deref().and_then(reminders::normalize_datetime)?;
+            Some((due, task))
+        })
+        .filter(|(due, _)| in_range(*due))
+        .collect();
+
+    tasks.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.id.cmp(&b.1.id)));
+    Ok(tasks.into_iter().map(|(_, task)| task).collect())
 }
 
-// TimeTracker Commands
+// Tasks that have sat "In Progress" for more than `days` days since their last status change
 #[tauri::command]
-fn get_time_entries(state: State<'_, DatabaseConnection>) -> Result<Vec<TimeEntry>, String> {
-    let conn = state.0.lock().map_err(|e| e.to_string())?;
+fn get_stale_in_progress(state: State<'_, DatabaseConnection>, days: i64) -> Result<Vec<Task>, String> {
+    if days < 0 {
+        return Err("Days cannot be negative".to_string());
+    }
+
+    let conn = state.lock()?;
     let mut stmt = conn
-        .prepare("SELECT id, task, start_time, end_time, duration, category, subject_id FROM time_entries ORDER BY start_time DESC")
+        .prepare(&format!(
+            "SELECT {} FROM tasks
+             WHERE status = ?1 AND datetime(status_changed_at) < datetime('now', ?2)
+             ORDER BY status_changed_at, id",
+            TASK_COLUMNS
+        ))
         .map_err(|e| e.to_string())?;
 
-    let entries = stmt
-        .query_map([], |row| {
-            Ok(TimeEntry {
-                id: row.get(0)?,
-                task: row.get(1)?,
-                start_time: row.get(2)?,
-                end_time: row.get(3)?,
-                duration: row.get(4)?,
-                category: row.get(5)?,
-                subject_id: row.get(6)?,
-            })
-        })
+    let tasks = stmt
+        .query_map(rusqlite::params![IN_PROGRESS_STATUS, format!("-{} days", days)], task_from_row)
         .map_err(|e| e.to_string())?;
 
-    entries.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())
+    tasks.collect::<SqliteResult<Vec<_>>>().map_err(|e| e.to_string())
 }
 
+// Open tasks due in the current local week, soonest first
 #[tauri::command]
-fn add_time_entry(state: State<'_, DatabaseConnection>, entry: TimeEntry) -> Result<Vec<TimeEntry>, String> {
-    let conn = state.0.lock().map_err(|e| e.to_string())?;
+fn get_tasks_this_week(state: State<'_, DatabaseConnection>) -> Result<Vec<Task>, String> {
+    let conn = state.lock()?;
+    let week_start_day = read_week_start_day(&conn).map_err(|e| e.to_string())?;
 
-    conn.execute(
-        "INSERT INTO time_entries (task, start_time, end_time, duration, category, subject_id) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-        rusqlite::params![
-            &entry.task,
-            &entry.start_time,
-            &entry.end_time,
-            entry.duration,
-            &entry.category,
-            entry.subject_id
-        ],
-    )
-    .map_err(|e| e.to_string())?;
+    let start = week_start(Local::now().date_naive(), week_start_day);
+    let end = start + chrono::Duration::days(7);
 
-    let mut stmt = conn
-        .prepare("SELECT id, task, start_time, end_time, duration, category, subject_id FROM time_entries ORDER BY start_time DESC")
-        .map_err(|e| e.to_string())?;
+    // Local midnights bounding the week; the end is exclusive
+    let local_midnight = |date: NaiveDate| {
+        reminders::resolve_local_datetime(&Local, date.and_time(chrono::NaiveTime::MIN))
+            .ok_or_else(|| format!("Cannot resolve local midnight on {}", date))
+    };
+    let start_dt = local_midnight(start)?;
+    let end_dt = local_midnight(end)?;
 
-    let entries = stmt
-        .query_map([], |row| {
-            Ok(TimeEntry {
-                id: row.get(0)?,
-                task: row.get(1)?,
-                start_time: row.get(2)?,
-                end_time: row.get(3)?,
-                duration: row.get(4)?,
-                category: row.get(5)?,
-                subject_id: row.get(6)?,
-            })
-        })
-        .map_err(|e| e.to_string())?;
+    load_tasks_due_where(&conn, false, |due| due >= start_dt && due < end_dt).map_err(|e| e.to_string())
+}
+
+// One query for search, filtering, sorting and paging; every value is bound, and the sort key
+// is mapped to a fixed expression
+#[tauri::command]
+fn query_tasks(state: State<'_, DatabaseConnection>, filter: TaskFilter) -> Result<Vec<Task>, String> {
+    let order_by = match filter.sort_by.as_deref() {
+        Some(by) => {
+            let expression = task_sort_expression(by).ok_or_else(|| {
+                format!("Cannot sort tasks by '{}', expected title, due_date, priority, status or created_at", by)
+            })?;
+            format!("{} {}, id", expression, if filter.desc { "DESC" } else { "ASC" })
+        }
+        None => "id".to_string(),
+    };
 
-    entries.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())
+    let conn = state.lock()?;
+    load_tasks_ordered(&conn, &filter, &order_by).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-fn update_time_entry(state: State<'_, DatabaseConnection>, entry: TimeEntry) -> Result<Vec<TimeEntry>, String> {
-    let conn = state.0.lock().map_err(|e| e.to_string())?;
+fn get_tasks_sorted(state: State<'_, DatabaseConnection>, by: String, desc: bool) -> Result<Vec<Task>, String> {
+    let expression = task_sort_expression(&by).ok_or_else(|| {
+        format!("Cannot sort tasks by '{}', expected title, due_date, priority, status or created_at", by)
+    })?;
+    let direction = if desc { "DESC" } else { "ASC" };
 
-    conn.execute(
-        "UPDATE time_entries SET task = ?1, start_time = ?2, end_time = ?3, duration = ?4, category = ?5, subject_id = ?6 WHERE id = ?7",
-        rusqlite::params![
-            &entry.task,
-            &entry.start_time,
-            &entry.end_time,
-            entry.duration,
-            &entry.category,
-            entry.subject_id,
-            entry.id
-        ],
-    )
-    .map_err(|e| e.to_string())?;
+    let conn = state.lock()?;
+    load_tasks_ordered(&conn, &TaskFilter::default(), &format!("{} {}, id", expression, direction))
+        .map_err(|e| e.to_string())
+}
 
-    let mut stmt = conn
-        .prepare("SELECT id, task, start_time, end_time, duration, category, subject_id FROM time_entries ORDER BY start_time DESC")
-        .map_err(|e| e.to_string())?;
+#[tauri::command]
+fn export_tasks(state: State<'_, DatabaseConnection>, path: String, format: String, filter: Option<TaskFilter>) -> Result<usize, String> {
+    let tasks = {
+        let conn = state.lock()?;
+        load_tasks_filtered(&conn, &filter.unwrap_or_default()).map_err(|e| e.to_string())?
+    };
 
-    let entries = stmt
-        .query_map([], |row| {
-            Ok(TimeEntry {
-                id: row.get(0)?,
-                task: row.get(1)?,
-                start_time: row.get(2)?,
-                end_time: row.get(3)?,
-                duration: row.get(4)?,
-                category: row.get(5)?,
-                subject_id: row.get(6)?,
-            })
-        })
-        .map_err(|e| e.to_string())?;
+    export::write_tasks(&tasks, &format, &path)?;
+    Ok(tasks.len())
+}
+
+#[tauri::command]
+fn add_task(state: State<'_, DatabaseConnection>, task: Task) -> Result<Vec<Task>, String> {
+    let conn = state.lock()?;
+    insert_task(&conn, &task)?;
+    load_tasks(&conn).map_err(|e| e.to_string())
+}
 
-    entries.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())
+#[tauri::command]
+fn add_task_returning_id(state: State<'_, DatabaseConnection>, task: Task) -> Result<i64, String> {
+    let conn = state.lock()?;
+    insert_task(&conn, &task)
 }
 
+// Title-only capture for a global hotkey: no tags or due date, priority and status from the
+// default_task_priority / default_task_status settings. Returns just the new id, without
+// reloading the task list.
 #[tauri::command]
-fn delete_time_entry(state: State<'_, DatabaseConnection>, id: i64) -> Result<Vec<TimeEntry>, String> {
-    let conn = state.0.lock().map_err(|e| e.to_string())?;
+fn quick_add_task(state: State<'_, DatabaseConnection>, title: String) -> Result<i64, String> {
+    let title = title.trim();
+    if title.is_empty() {
+        return Err("Task title cannot be empty".to_string());
+    }
 
-    conn.execute("DELETE FROM time_entries WHERE id = ?1", rusqlite::params![id])
-        .map_err(|e| e.to_string())?;
+    let conn = state.lock()?;
+    let (priority, status) = load_task_defaults(&conn).map_err(|e| e.to_string())?;
 
-    let mut stmt = conn
-        .prepare("SELECT id, task, start_time, end_time, duration, category, subject_id FROM time_entries ORDER BY start_time DESC")
-        .map_err(|e| e.to_string())?;
+    insert_task(&conn, &Task {
+        id: 0,
+        title: title.to_string(),
+        description: String::new(),
+        due_date: None,
+        tags: Vec::new(),
+        priority,
+        status,
+        subject_id: None,
+        estimated_minutes: None,
+        actual_minutes: None,
+        is_pinned: false,
+        created_at: None,
+        status_changed_at: None,
+        completed_at: None,
+        color: None,
+    })
+}
 
-    let entries = stmt
-        .query_map([], |row| {
-            Ok(TimeEntry {
-                id: row.get(0)?,
-                task: row.get(1)?,
-                start_time: row.get(2)?,
-                end_time: row.get(3)?,
-                duration: row.get(4)?,
-                category: row.get(5)?,
-                subject_id: row.get(6)?,
+// Status, priority and due date as currently stored, for change tracking
+struct TrackedTaskFields {
+    status: String,
+    priority: String,
+    due_date: String,
+}
+
+fn load_tracked_fields(conn: &Connection, task_id: i64) -> SqliteResult<Option<TrackedTaskFields>> {
+    conn.query_row(
+        "SELECT status, priority, due_date FROM tasks WHERE id = ?1",
+        rusqlite::params![task_id],
+        |row| {
+            Ok(TrackedTaskFields {
+                status: row.get(0)?,
+                priority: row.get(1)?,
+                due_date: row.get::<_, Option<String>>(2)?.unwrap_or_default(),
             })
-        })
-        .map_err(|e| e.to_string())?;
+        },
+    )
+    .optional()
+}
 
-    entries.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())
+fn record_task_change(conn: &Connection, task_id: i64, field: &str, old_value: &str, new_value: &str) -> SqliteResult<()> {
+    if old_value != new_value {
+        conn.execute(
+            "INSERT INTO task_history (task_id, field, old_value, new_value) VALUES (?1, ?2, ?3, ?4)",
+            rusqlite::params![task_id, field, old_value, new_value],
+        )?;
+    }
+    Ok(())
 }
 
-// Spending Commands
-#[tauri::command]
-fn get_expenses(state: State<'_, DatabaseConnection>) -> Result<Vec<Expense>, String> {
-    let conn = state.0.lock().map_err(|e| e.to_string())?;
-    let mut stmt = conn
-        .prepare("SELECT id, amount, description, category, date, expense_type FROM expenses ORDER BY date DESC")
-        .map_err(|e| e.to_string())?;
+// Stamps status_changed_at (UTC) on any status change, and completed_at when a task becomes
+// Done (cleared again when it is reopened)
+fn update_status_timestamps(conn: &Connection, task_id: i64, previous_status: &str, status: &str) -> SqliteResult<()> {
+    if previous_status == status {
+        return Ok(());
+    }
 
-    let expenses = stmt
-        .query_map([], |row| {
-            Ok(Expense {
-                id: row.get(0)?,
-                amount: row.get(1)?,
-                description: row.get(2)?,
-                category: row.get(3)?,
-                date: row.get(4)?,
-                expense_type: row.get(5)?,
-            })
-        })
-        .map_err(|e| e.to_string())?;
+    conn.execute("UPDATE tasks SET status_changed_at = datetime('now') WHERE id = ?1", rusqlite::params![task_id])?;
+
+    if status == "Done" {
+        conn.execute("UPDATE tasks SET completed_at = datetime('now') WHERE id = ?1", rusqlite::params![task_id])?;
+    } else if previous_status == "Done" {
+        conn.execute("UPDATE tasks SET completed_at = NULL WHERE id = ?1", rusqlite::params![task_id])?;
+    }
+    Ok(())
+}
+
+// Opt-in: moving a task into "In Progress" starts a timer for it, moving it out stops the timer
+const AUTO_TIMER_KEY: &str = "auto_timer_for_in_progress";
+const IN_PROGRESS_STATUS: &str = "In Progress";
+
+fn sync_auto_timer(conn: &Connection, task_id: i64, previous_status: &str, status: &str) -> SqliteResult<()> {
+    if previous_status == status || read_setting(conn, AUTO_TIMER_KEY)?.as_deref() != Some("true") {
+        return Ok(());
+    }
+
+    let now = Utc::now();
+    let now_str = now.to_rfc3339_opts(SecondsFormat::Millis, true);
+
+    if status == IN_PROGRESS_STATUS {
+        // Never start a second timer while one is already running for the task
+        let running: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM time_entries WHERE task_id = ?1 AND end_time IS NULL",
+            rusqlite::params![task_id],
+            |row| row.get(0),
+        )?;
+        if running > 0 {
+            return Ok(());
+        }
+
+        let (title, tags): (String, String) = conn.query_row(
+            "SELECT title, tags FROM tasks WHERE id = ?1",
+            rusqlite::params![task_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        let category = serde_json::from_str::<Vec<String>>(&tags)
+            .ok()
+            .and_then(|tags| tags.into_iter().next())
+            .unwrap_or_else(|| "Work".to_string());
 
-    expenses.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())
+        insert_time_entry(conn, &TimeEntry {
+            id: 0,
+            task: title,
+            start_time: now_str,
+            end_time: None,
+            duration: 0,
+            category,
+            subject_id: None,
+            task_id: Some(task_id),
+            archived: false,
+            description: String::new(),
+        })?;
+    } else if previous_status == IN_PROGRESS_STATUS {
+        let mut stmt = conn.prepare("SELECT id, start_time FROM time_entries WHERE task_id = ?1 AND end_time IS NULL")?;
+        let running = stmt
+            .query_map(rusqlite::params![task_id], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))?
+            .collect::<SqliteResult<Vec<_>>>()?;
+
+        let rounding = load_time_rounding(conn)?;
+        for (id, start_time) in running {
+            let duration = DateTime::parse_from_rfc3339(&start_time)
+                .map(|start| (now - start.with_timezone(&Utc)).num_seconds().max(0))
+                .unwrap_or(0);
+            let (duration, end_time) = rounding
+                .and_then(|interval| rounded_span(&start_time, duration, interval))
+                .unwrap_or((duration, now_str.clone()));
+            conn.execute(
+                "UPDATE time_entries SET end_time = ?1, duration = ?2 WHERE id = ?3",
+                rusqlite::params![&end_time, duration, id],
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+fn record_task_changes(conn: &Connection, task_id: i64, previous: &TrackedTaskFields, current: &TrackedTaskFields) -> SqliteResult<()> {
+    record_task_change(conn, task_id, "status", &previous.status, &current.status)?;
+    record_task_change(conn, task_id, "priority", &previous.priority, &current.priority)?;
+    record_task_change(conn, task_id, "due_date", &previous.due_date, &current.due_date)
 }
 
+// `delete_reminders_on_clear`: when the due date is removed, delete the task's reminders
+// instead of keeping them at their current times (the default)
 #[tauri::command]
-fn add_expense(state: State<'_, DatabaseConnection>, expense: Expense) -> Result<Vec<Expense>, String> {
-    let conn = state.0.lock().map_err(|e| e.to_string())?;
+fn update_task(state: State<'_, DatabaseConnection>, task: Task, delete_reminders_on_clear: Option<bool>) -> Result<Vec<Task>, String> {
+    let mut conn = state.lock()?;
 
-    conn.execute(
-        "INSERT INTO expenses (amount, description, category, date, expense_type) VALUES (?1, ?2, ?3, ?4, ?5)",
+    let tags = validate_tags(&conn, &task.tags)?;
+    let tags_json = serde_json::to_string(&tags).map_err(|e| e.to_string())?;
+    let due_date = stored_due_date(task.due_date.as_deref());
+    let color = validate_color(task.color.as_deref())?;
+
+    // History is written in the same transaction so it never drifts from the task itself
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+
+    let previous = load_tracked_fields(&tx, task.id as i64).map_err(|e| e.to_string())?;
+    let previous_due_date = previous.as_ref().map(|fields| fields.due_date.clone());
+
+    tx.execute(
+        "UPDATE tasks SET title = ?1, description = ?2, due_date = ?3, tags = ?4, priority = ?5, status = ?6, subject_id = ?7, estimated_minutes = ?8, actual_minutes = ?9, color = ?10 WHERE id = ?11",
         rusqlite::params![
-            expense.amount,
-            &expense.description,
-            &expense.category,
-            &expense.date,
-            &expense.expense_type
+            &task.title,
+            &task.description,
+            &due_date,
+            &tags_json,
+            &task.priority,
+            &task.status,
+            task.subject_id,
+            task.estimated_minutes.unwrap_or(60),
+            task.actual_minutes.unwrap_or(0),
+            &color,
+            task.id as i64
         ],
+    ).map_err(|e| e.to_string())?;
+
+    if let Some(ref previous) = previous {
+        let current = TrackedTaskFields {
+            status: task.status.clone(),
+            priority: task.priority.clone(),
+            due_date: due_date.clone().unwrap_or_default(),
+        };
+        record_task_changes(&tx, task.id as i64, previous, &current).map_err(|e| e.to_string())?;
+        update_status_timestamps(&tx, task.id as i64, &previous.status, &current.status).map_err(|e| e.to_string())?;
+        sync_auto_timer(&tx, task.id as i64, &previous.status, &current.status).map_err(|e| e.to_string())?;
+    }
+
+    reminders::recalculate_reminders_for_task(
+        &tx,
+        task.id as i64,
+        previous_due_date.as_deref(),
+        due_date.as_deref(),
     )
     .map_err(|e| e.to_string())?;
 
-    let mut stmt = conn
-        .prepare("SELECT id, amount, description, category, date, expense_type FROM expenses ORDER BY date DESC")
-        .map_err(|e| e.to_string())?;
+    let due_date_cleared = previous_due_date.is_some_and(|previous| !previous.is_empty()) && due_date.is_none();
+    if due_date_cleared {
+        reminders::handle_cleared_due_date(&tx, task.id as i64, delete_reminders_on_clear.unwrap_or(false))
+            .map_err(|e| e.to_string())?;
+    }
 
-    let expenses = stmt
-        .query_map([], |row| {
-            Ok(Expense {
-                id: row.get(0)?,
-                amount: row.get(1)?,
-                description: row.get(2)?,
-                category: row.get(3)?,
-                date: row.get(4)?,
-                expense_type: row.get(5)?,
-            })
-        })
-        .map_err(|e| e.to_string())?;
+    tx.commit().map_err(|e| e.to_string())?;
 
-    expenses.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())
+    load_tasks(&conn).map_err(|e| e.to_string())
 }
 
+// `due_date`: None leaves it alone, Some(None) clears it. Tauri maps both a missing key and
+// an explicit null to None, so the frontend clears a due date by sending an empty string
+// (stored as NULL).
+// `delete_reminders_on_clear` behaves as in update_task.
 #[tauri::command]
-fn update_expense(state: State<'_, DatabaseConnection>, expense: Expense) -> Result<Vec<Expense>, String> {
-    let conn = state.0.lock().map_err(|e| e.to_string())?;
+fn patch_task(
+    state: State<'_, DatabaseConnection>,
+    id: u64,
+    status: Option<String>,
+    priority: Option<String>,
+    due_date: Option<Option<String>>,
+    delete_reminders_on_clear: Option<bool>,
+) -> Result<Vec<Task>, String> {
+    let mut conn = state.lock()?;
+    apply_task_patch(&mut conn, id, status, priority, due_date, delete_reminders_on_clear)?;
+    load_tasks(&conn).map_err(|e| e.to_string())
+}
 
-    conn.execute(
-        "UPDATE expenses SET amount = ?1, description = ?2, category = ?3, date = ?4, expense_type = ?5 WHERE id = ?6",
-        rusqlite::params![
-            expense.amount,
-            &expense.description,
-            &expense.category,
-            &expense.date,
-            &expense.expense_type,
-            expense.id
-        ],
-    )
-    .map_err(|e| e.to_string())?;
+fn apply_task_patch(
+    conn: &mut Connection,
+    id: u64,
+    status: Option<String>,
+    priority: Option<String>,
+    due_date: Option<Option<String>>,
+    delete_reminders_on_clear: Option<bool>,
+) -> Result<(), String> {
+    // Checked up front so an unknown value never reaches the SET clause
+    if let Some(ref status) = status {
+        if !TASK_STATUSES.contains(&status.as_str()) {
+            return Err(format!("Unknown status '{}', expected {}", status, TASK_STATUSES.join(", ")));
+        }
+    }
+    if let Some(ref priority) = priority {
+        if !TASK_PRIORITIES.contains(&priority.as_str()) {
+            return Err(format!("Unknown priority '{}', expected {}", priority, TASK_PRIORITIES.join(", ")));
+        }
+    }
 
-    let mut stmt = conn
-        .prepare("SELECT id, amount, description, category, date, expense_type FROM expenses ORDER BY date DESC")
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+
+    let previous = load_tracked_fields(&tx, id as i64)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Task not found".to_string())?;
+
+    let due_date = due_date.map(|d| stored_due_date(d.as_deref()));
+
+    let mut assignments: Vec<&str> = Vec::new();
+    let mut values: Vec<&dyn rusqlite::ToSql> = Vec::new();
+    if let Some(ref status) = status {
+        assignments.push("status = ?");
+        values.push(status);
+    }
+    if let Some(ref priority) = priority {
+        assignments.push("priority = ?");
+        values.push(priority);
+    }
+    if let Some(ref due_date) = due_date {
+        assignments.push("due_date = ?");
+        values.push(due_date);
+    }
+
+    if !assignments.is_empty() {
+        let task_id = id as i64;
+        values.push(&task_id);
+        tx.execute(
+            &format!("UPDATE tasks SET {} WHERE id = ?", assignments.join(", ")),
+            values.as_slice(),
+        )
         .map_err(|e| e.to_string())?;
+    }
 
-    let expenses = stmt
-        .query_map([], |row| {
-            Ok(Expense {
-                id: row.get(0)?,
-                amount: row.get(1)?,
-                description: row.get(2)?,
-                category: row.get(3)?,
-                date: row.get(4)?,
-                expense_type: row.get(5)?,
-            })
-        })
+    let current = TrackedTaskFields {
+        status: status.unwrap_or_else(|| previous.status.clone()),
+        priority: priority.unwrap_or_else(|| previous.priority.clone()),
+        due_date: match due_date {
+            Some(ref due_date) => due_date.clone().unwrap_or_default(),
+            None => previous.due_date.clone(),
+        },
+    };
+    record_task_changes(&tx, id as i64, &previous, &current).map_err(|e| e.to_string())?;
+    update_status_timestamps(&tx, id as i64, &previous.status, &current.status).map_err(|e| e.to_string())?;
+    sync_auto_timer(&tx, id as i64, &previous.status, &current.status).map_err(|e| e.to_string())?;
+
+    if let Some(ref due_date) = due_date {
+        reminders::recalculate_reminders_for_task(
+            &tx,
+            id as i64,
+            Some(previous.due_date.as_str()),
+            due_date.as_deref(),
+        )
         .map_err(|e| e.to_string())?;
 
-    expenses.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())
+        if !previous.due_date.is_empty() && due_date.is_none() {
+            reminders::handle_cleared_due_date(&tx, id as i64, delete_reminders_on_clear.unwrap_or(false))
+                .map_err(|e| e.to_string())?;
+        }
+    }
+
+    tx.commit().map_err(|e| e.to_string())
 }
 
+// The "check the box" path: changes only the status, so fields edited elsewhere aren't
+// overwritten, with the same timestamps, history and auto-timer handling as update_task
 #[tauri::command]
-fn delete_expense(state: State<'_, DatabaseConnection>, id: i64) -> Result<Vec<Expense>, String> {
-    let conn = state.0.lock().map_err(|e| e.to_string())?;
+fn set_task_status(state: State<'_, DatabaseConnection>, id: u64, status: String) -> Result<Vec<Task>, String> {
+    if !TASK_STATUSES.contains(&status.as_str()) {
+        return Err(format!("Unknown status '{}', expected {}", status, TASK_STATUSES.join(", ")));
+    }
 
-    conn.execute("DELETE FROM expenses WHERE id = ?1", rusqlite::params![id])
-        .map_err(|e| e.to_string())?;
+    let mut conn = state.lock()?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let task_id = id as i64;
 
-    let mut stmt = conn
-        .prepare("SELECT id, amount, description, category, date, expense_type FROM expenses ORDER BY date DESC")
-        .map_err(|e| e.to_string())?;
+    let previous = load_tracked_fields(&tx, task_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Task not found".to_string())?;
 
-    let expenses = stmt
-        .query_map([], |row| {
-            Ok(Expense {
-                id: row.get(0)?,
-                amount: row.get(1)?,
-                description: row.get(2)?,
-                category: row.get(3)?,
-                date: row.get(4)?,
-                expense_type: row.get(5)?,
-            })
-        })
+    tx.execute("UPDATE tasks SET status = ?1 WHERE id = ?2", rusqlite::params![&status, task_id])
         .map_err(|e| e.to_string())?;
 
-    expenses.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())
+    let current = TrackedTaskFields {
+        status,
+        priority: previous.priority.clone(),
+        due_date: previous.due_date.clone(),
+    };
+    record_task_changes(&tx, task_id, &previous, &current).map_err(|e| e.to_string())?;
+    update_status_timestamps(&tx, task_id, &previous.status, &current.status).map_err(|e| e.to_string())?;
+    sync_auto_timer(&tx, task_id, &previous.status, &current.status).map_err(|e| e.to_string())?;
+
+    tx.commit().map_err(|e| e.to_string())?;
+
+    load_tasks(&conn).map_err(|e| e.to_string())
 }
 
-// ============================================================================
-// Subject Commands
-// ============================================================================
+// Moves a task's due date to `day` (local), keeping its time of day, or 23:59 when it had no
+// readable due date. Reminders move with the due date and the change is recorded, as in
+// patch_task.
+fn move_due_date_to_day(conn: &Connection, task_id: i64, day: NaiveDate) -> Result<(), String> {
+    let previous = load_tracked_fields(conn, task_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Task {} not found", task_id))?;
 
-#[tauri::command]
-fn get_subjects(state: State<'_, DatabaseConnection>) -> Result<Vec<Subject>, String> {
-    let conn = state.0.lock().map_err(|e| e.to_string())?;
-    let mut stmt = conn
-        .prepare("SELECT id, name, color, semester, credits FROM subjects ORDER BY name")
-        .map_err(|e| e.to_string())?;
+    let time = reminders::normalize_datetime(&previous.due_date)
+        .map(|due| due.with_timezone(&Local).time())
+        .unwrap_or_else(|| chrono::NaiveTime::from_hms_opt(23, 59, 0).unwrap_or_default());
+    let due_date = day.and_time(time).format("%Y-%m-%dT%H:%M").to_string();
 
-    let subjects = stmt
-        .query_map([], |row| {
-            Ok(Subject {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                color: row.get(2)?,
-                semester: row.get(3)?,
-                credits: row.get(4)?,
-            })
-        })
+    conn.execute("UPDATE tasks SET due_date = ?1 WHERE id = ?2", rusqlite::params![&due_date, task_id])
         .map_err(|e| e.to_string())?;
 
-    subjects.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())
+    let current = TrackedTaskFields {
+        status: previous.status.clone(),
+        priority: previous.priority.clone(),
+        due_date: due_date.clone(),
+    };
+    record_task_changes(conn, task_id, &previous, &current).map_err(|e| e.to_string())?;
+
+    reminders::recalculate_reminders_for_task(conn, task_id, Some(previous.due_date.as_str()), Some(due_date.as_str()))
+        .map_err(|e| e.to_string())
 }
 
+// Moves each task to today + `days_from_now`; any unknown id rolls the whole batch back
 #[tauri::command]
-fn add_subject(state: State<'_, DatabaseConnection>, subject: Subject) -> Result<Vec<Subject>, String> {
-    let conn = state.0.lock().map_err(|e| e.to_string())?;
+fn bulk_set_due_date(state: State<'_, DatabaseConnection>, ids: Vec<u64>, days_from_now: i64) -> Result<Vec<Task>, String> {
+    let target_day = chrono::Duration::try_days(days_from_now)
+        .and_then(|offset| Local::now().date_naive().checked_add_signed(offset))
+        .ok_or_else(|| format!("{} days from now is out of range", days_from_now))?;
 
-    conn.execute(
-        "INSERT INTO subjects (name, color, semester, credits) VALUES (?1, ?2, ?3, ?4)",
-        rusqlite::params![&subject.name, &subject.color, &subject.semester, subject.credits],
-    )
-    .map_err(|e| e.to_string())?;
+    let mut conn = state.lock()?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
 
-    drop(conn);
-    let state_clone = state.clone();
-    get_subjects(state_clone)
+    for id in ids {
+        move_due_date_to_day(&tx, id as i64, target_day)?;
+    }
+
+    tx.commit().map_err(|e| e.to_string())?;
+
+    load_tasks(&conn).map_err(|e| e.to_string())
 }
 
+// Moves every open overdue task to today, keeping its time of day; tasks without a due date
+// are never overdue, so they are left alone
 #[tauri::command]
-fn update_subject(state: State<'_, DatabaseConnection>, subject: Subject) -> Result<Vec<Subject>, String> {
-    let conn = state.0.lock().map_err(|e| e.to_string())?;
+fn reschedule_overdue_to_today(state: State<'_, DatabaseConnection>) -> Result<Vec<Task>, String> {
+    let today = Local::now().date_naive();
 
-    conn.execute(
-        "UPDATE subjects SET name = ?1, color = ?2, semester = ?3, credits = ?4 WHERE id = ?5",
-        rusqlite::params![&subject.name, &subject.color, &subject.semester, subject.credits, subject.id],
-    )
-    .map_err(|e| e.to_string())?;
+    let mut conn = state.lock()?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
 
-    drop(conn);
-    get_subjects(state)
+    let overdue_ids = {
+        let mut stmt = tx
+            .prepare(&format!("SELECT id FROM tasks WHERE {}", OVERDUE_TASK_FILTER))
+            .map_err(|e| e.to_string())?;
+        let ids = stmt
+            .query_map([], |row| row.get::<_, i64>(0))
+            .map_err(|e| e.to_string())?
+            .collect::<SqliteResult<Vec<_>>>()
+            .map_err(|e| e.to_string())?;
+        ids
+    };
+
+    for id in overdue_ids {
+        move_due_date_to_day(&tx, id, today)?;
+    }
+
+    tx.commit().map_err(|e| e.to_string())?;
+
+    load_tasks(&conn).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-fn delete_subject(state: State<'_, DatabaseConnection>, id: i64) -> Result<Vec<Subject>, String> {
-    let conn = state.0.lock().map_err(|e| e.to_string())?;
+fn toggle_pin(state: State<'_, DatabaseConnection>, id: u64) -> Result<Vec<Task>, String> {
+    let conn = state.lock()?;
 
-    conn.execute("DELETE FROM subjects WHERE id = ?1", rusqlite::params![id])
+    let updated = conn
+        .execute("UPDATE tasks SET is_pinned = NOT is_pinned WHERE id = ?1", rusqlite::params![id as i64])
+        .map_err(|e| e.to_string())?;
+    if updated == 0 {
+        return Err("Task not found".to_string());
+    }
+
+    load_tasks(&conn).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn get_task_history(state: State<'_, DatabaseConnection>, task_id: u64) -> Result<Vec<TaskHistoryEntry>, String> {
+    let conn = state.lock()?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, task_id, field, old_value, new_value, changed_at FROM task_history
+             WHERE task_id = ?1 ORDER BY changed_at, id",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let entries = stmt
+        .query_map(rusqlite::params![task_id as i64], |row| {
+            Ok(TaskHistoryEntry {
+                id: row.get(0)?,
+                task_id: row.get(1)?,
+                field: row.get(2)?,
+                old_value: row.get(3)?,
+                new_value: row.get(4)?,
+                changed_at: row.get(5)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+
+    entries.collect::<SqliteResult<Vec<_>>>().map_err(|e| e.to_string())
+}
+
+fn load_task_notes(conn: &Connection, task_id: i64) -> SqliteResult<Vec<TaskNote>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, task_id, body, created_at FROM task_notes WHERE task_id = ?1 ORDER BY created_at, id",
+    )?;
+
+    let notes = stmt.query_map(rusqlite::params![task_id], |row| {
+        Ok(TaskNote {
+            id: row.get(0)?,
+            task_id: row.get(1)?,
+            body: row.get(2)?,
+            created_at: row.get(3)?,
+        })
+    })?;
+
+    notes.collect()
+}
+
+#[tauri::command]
+fn add_task_note(state: State<'_, DatabaseConnection>, task_id: u64, body: String) -> Result<Vec<TaskNote>, String> {
+    let body = body.trim();
+    if body.is_empty() {
+        return Err("Note cannot be empty".to_string());
+    }
+
+    let conn = state.lock()?;
+
+    if load_tracked_fields(&conn, task_id as i64).map_err(|e| e.to_string())?.is_none() {
+        return Err("Task not found".to_string());
+    }
+
+    conn.execute(
+        "INSERT INTO task_notes (task_id, body) VALUES (?1, ?2)",
+        rusqlite::params![task_id as i64, body],
+    )
+    .map_err(|e| e.to_string())?;
+
+    load_task_notes(&conn, task_id as i64).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn get_task_notes(state: State<'_, DatabaseConnection>, task_id: u64) -> Result<Vec<TaskNote>, String> {
+    let conn = state.lock()?;
+    load_task_notes(&conn, task_id as i64).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn get_task_detail(state: State<'_, DatabaseConnection>, id: u64) -> Result<TaskDetail, String> {
+    let conn = state.lock()?;
+    let task_id = id as i64;
+
+    let task = load_task(&conn, task_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Task not found".to_string())?;
+
+    Ok(TaskDetail {
+        task,
+        reminders: reminders::get_reminders_for_task(&conn, task_id, false)?,
+        notes: load_task_notes(&conn, task_id).map_err(|e| e.to_string())?,
+        time_entries: load_time_entries_for_task(&conn, task_id).map_err(|e| e.to_string())?,
+    })
+}
+
+#[tauri::command]
+fn delete_task_note(state: State<'_, DatabaseConnection>, note_id: i64) -> Result<(), String> {
+    let conn = state.lock()?;
+
+    let deleted = conn
+        .execute("DELETE FROM task_notes WHERE id = ?1", rusqlite::params![note_id])
+        .map_err(|e| e.to_string())?;
+    if deleted == 0 {
+        return Err("Note not found".to_string());
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+fn delete_task(state: State<'_, DatabaseConnection>, id: u64) -> Result<Vec<Task>, String> {
+    let conn = state.lock()?;
+
+    conn.execute(
+        "DELETE FROM tasks WHERE id = ?1",
+        rusqlite::params![id as i64]
+    ).map_err(|e| e.to_string())?;
+
+    load_tasks(&conn).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn find_duplicate_tasks(state: State<'_, DatabaseConnection>) -> Result<Vec<Vec<Task>>, String> {
+    let conn = state.lock()?;
+    let tasks = load_tasks(&conn).map_err(|e| e.to_string())?;
+
+    // Group by lowercased title with whitespace runs collapsed, keeping first-seen order
+    let mut index: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+    let mut groups: Vec<Vec<Task>> = Vec::new();
+    for task in tasks {
+        let key = task
+            .title
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase();
+        match index.get(&key) {
+            Some(&i) => groups[i].push(task),
+            None => {
+                index.insert(key, groups.len());
+                groups.push(vec![task]);
+            }
+        }
+    }
+
+    Ok(groups.into_iter().filter(|members| members.len() > 1).collect())
+}
+
+// Tags appearing on the same tasks as `tag`, most frequent first
+#[tauri::command]
+fn get_related_tags(state: State<'_, DatabaseConnection>, tag: String) -> Result<Vec<(String, u64)>, String> {
+    let tag = tag.trim().to_lowercase();
+    if tag.is_empty() {
+        return Err("Tag cannot be empty".to_string());
+    }
+
+    let conn = state.lock()?;
+    let filter = TaskFilter {
+        tag: Some(tag.clone()),
+        ..TaskFilter::default()
+    };
+    let tasks = load_tasks_filtered(&conn, &filter).map_err(|e| e.to_string())?;
+
+    let mut counts: std::collections::HashMap<String, u64> = std::collections::HashMap::new();
+    for task in tasks {
+        for other in task.tags {
+            if other.to_lowercase() != tag {
+                *counts.entry(other).or_insert(0) += 1;
+            }
+        }
+    }
+
+    let mut related: Vec<(String, u64)> = counts.into_iter().collect();
+    related.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    Ok(related)
+}
+
+#[tauri::command]
+fn get_overdue_by_priority(state: State<'_, DatabaseConnection>) -> Result<Vec<(String, u64)>, String> {
+    let conn = state.lock()?;
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT priority, COUNT(*) FROM tasks
+             WHERE {}
+             GROUP BY priority
+             ORDER BY CASE priority WHEN 'High' THEN 0 WHEN 'Medium' THEN 1 WHEN 'Low' THEN 2 ELSE 3 END",
+            OVERDUE_TASK_FILTER
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let counts = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get::<_, i64>(1)? as u64)))
+        .map_err(|e| e.to_string())?;
+
+    counts.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn create_reminder(state: State<'_, DatabaseConnection>, task_id: i64, remind_at: String, force: Option<bool>) -> Result<(), String> {
+    let conn = state.lock()?;
+    reminders::create_reminder(&conn, task_id, remind_at, force.unwrap_or(false))
+}
+
+#[tauri::command]
+fn get_reminders_for_task(
+    state: State<'_, DatabaseConnection>,
+    task_id: i64,
+    descending: Option<bool>,
+) -> Result<Vec<reminders::Reminder>, String> {
+    let conn = state.lock()?;
+    reminders::get_reminders_for_task(&conn, task_id, descending.unwrap_or(false))
+}
+
+#[tauri::command]
+fn get_reminders_for_task_detailed(state: State<'_, DatabaseConnection>, task_id: i64) -> Result<Vec<reminders::ReminderWithTask>, String> {
+    let conn = state.lock()?;
+    reminders::get_reminders_for_task_detailed(&conn, task_id)
+}
+
+#[tauri::command]
+fn get_tasks_with_upcoming_reminders(state: State<'_, DatabaseConnection>, hours: i64) -> Result<Vec<reminders::ReminderWithTask>, String> {
+    let conn = state.lock()?;
+    reminders::get_upcoming_reminders(&conn, hours)
+}
+
+#[tauri::command]
+fn delete_reminder(state: State<'_, DatabaseConnection>, reminder_id: i64) -> Result<(), String> {
+    let conn = state.lock()?;
+    reminders::delete_reminder(&conn, reminder_id)
+}
+
+#[tauri::command]
+fn reset_reminder(state: State<'_, DatabaseConnection>, reminder_id: i64) -> Result<(), String> {
+    let conn = state.lock()?;
+    reminders::reset_reminder(&conn, reminder_id)
+}
+
+#[tauri::command]
+fn acknowledge_reminder(state: State<'_, DatabaseConnection>, reminder_id: i64) -> Result<(), String> {
+    let conn = state.lock()?;
+    reminders::acknowledge_reminder(&conn, reminder_id)
+}
+
+#[tauri::command]
+fn get_pending_reminders(
+    state: State<'_, DatabaseConnection>,
+    include_unacknowledged: Option<bool>,
+) -> Result<Vec<reminders::ReminderWithTask>, String> {
+    let conn = state.lock()?;
+    reminders::get_pending_reminders(&conn, include_unacknowledged.unwrap_or(false))
+}
+
+// None when nothing is scheduled
+#[tauri::command]
+fn get_next_reminder(state: State<'_, DatabaseConnection>) -> Result<Option<reminders::ReminderWithTask>, String> {
+    let conn = state.lock()?;
+    reminders::next_reminder(&conn)
+}
+
+// A dead or not yet started worker is reported with alive = false rather than as an error
+#[tauri::command]
+fn get_worker_status() -> Result<reminders::WorkerStatus, String> {
+    Ok(reminders::worker_status())
+}
+
+#[tauri::command]
+fn get_reminder_counts(state: State<'_, DatabaseConnection>) -> Result<(u64, u64), String> {
+    let conn = state.lock()?;
+    reminders::get_reminder_counts(&conn)
+}
+
+#[tauri::command]
+fn cleanup_old_reminders(state: State<'_, DatabaseConnection>, days: i64) -> Result<u64, String> {
+    let conn = state.lock()?;
+    reminders::cleanup_old_reminders(&conn, days)
+}
+
+#[tauri::command]
+fn export_reminders(state: State<'_, DatabaseConnection>, path: String) -> Result<(), String> {
+    let schedule = {
+        let conn = state.lock()?;
+        reminders::load_reminder_schedule(&conn)?
+    };
+    export::write_reminders(&schedule, &path)
+}
+
+// All-or-nothing: a database error rolls back every reminder from the file
+#[tauri::command]
+fn import_reminders(state: State<'_, DatabaseConnection>, path: String) -> Result<reminders::ReminderImportReport, String> {
+    let schedule = export::read_reminders(&path)?;
+
+    let mut conn = state.lock()?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let report = reminders::import_reminder_schedule(&tx, &schedule)?;
+    tx.commit().map_err(|e| e.to_string())?;
+
+    Ok(report)
+}
+
+#[tauri::command]
+fn find_orphan_reminders(state: State<'_, DatabaseConnection>) -> Result<Vec<reminders::Reminder>, String> {
+    let conn = state.lock()?;
+    reminders::find_orphan_reminders(&conn)
+}
+
+#[tauri::command]
+fn delete_orphan_reminders(state: State<'_, DatabaseConnection>) -> Result<u64, String> {
+    let conn = state.lock()?;
+    reminders::delete_orphan_reminders(&conn)
+}
+
+#[tauri::command]
+fn remind_all_overdue(state: State<'_, DatabaseConnection>, offset_minutes: i64) -> Result<Vec<reminders::Reminder>, String> {
+    let conn = state.lock()?;
+    reminders::remind_all_overdue(&conn, offset_minutes)
+}
+
+#[tauri::command]
+fn shift_task_reminders(state: State<'_, DatabaseConnection>, task_id: i64, minutes: i64) -> Result<Vec<reminders::Reminder>, String> {
+    let mut conn = state.lock()?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let shifted = reminders::shift_task_reminders(&tx, task_id, minutes)?;
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(shifted)
+}
+
+#[tauri::command]
+fn copy_reminders(state: State<'_, DatabaseConnection>, from_task_id: i64, to_task_id: i64) -> Result<Vec<reminders::Reminder>, String> {
+    let conn = state.lock()?;
+    reminders::copy_reminders(&conn, from_task_id, to_task_id)
+}
+
+// TimeTracker Commands
+const TIME_ENTRY_COLUMNS: &str = "id, task, start_time, end_time, duration, category, subject_id, task_id, archived, description";
+
+fn time_entry_from_row(row: &rusqlite::Row) -> SqliteResult<TimeEntry> {
+    Ok(TimeEntry {
+        id: row.get(0)?,
+        task: row.get(1)?,
+        start_time: row.get(2)?,
+        end_time: row.get(3)?,
+        duration: row.get(4)?,
+        category: row.get(5)?,
+        subject_id: row.get(6)?,
+        task_id: row.get(7)?,
+        archived: row.get(8)?,
+        description: row.get(9)?,
+    })
+}
+
+// `where_clause` is trusted SQL, never user input
+fn select_time_entries(conn: &Connection, where_clause: &str) -> SqliteResult<Vec<TimeEntry>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM time_entries {} ORDER BY start_time DESC",
+        TIME_ENTRY_COLUMNS, where_clause
+    ))?;
+    let entries = stmt.query_map([], time_entry_from_row)?;
+    entries.collect()
+}
+
+// Active (unarchived) entries, newest first
+fn load_time_entries(conn: &Connection) -> SqliteResult<Vec<TimeEntry>> {
+    select_time_entries(conn, "WHERE archived = 0")
+}
+
+fn load_time_entries_for_task(conn: &Connection, task_id: i64) -> SqliteResult<Vec<TimeEntry>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM time_entries WHERE task_id = ?1 ORDER BY start_time DESC",
+        TIME_ENTRY_COLUMNS
+    ))?;
+    let entries = stmt.query_map(rusqlite::params![task_id], time_entry_from_row)?;
+    entries.collect()
+}
+
+fn load_time_entry(conn: &Connection, id: i64) -> SqliteResult<Option<TimeEntry>> {
+    conn.query_row(
+        &format!("SELECT {} FROM time_entries WHERE id = ?1", TIME_ENTRY_COLUMNS),
+        rusqlite::params![id],
+        time_entry_from_row,
+    )
+    .optional()
+}
+
+fn insert_time_entry(conn: &Connection, entry: &TimeEntry) -> SqliteResult<i64> {
+    conn.execute(
+        "INSERT INTO time_entries (task, start_time, end_time, duration, category, subject_id, task_id, archived, description) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        rusqlite::params![
+            &entry.task,
+            &entry.start_time,
+            &entry.end_time,
+            entry.duration,
+            &entry.category,
+            entry.subject_id,
+            entry.task_id,
+            entry.archived,
+            &entry.description
+        ],
+    )?;
+
+    Ok(conn.last_insert_rowid())
+}
+
+#[tauri::command]
+fn get_time_entries(state: State<'_, DatabaseConnection>) -> Result<Vec<TimeEntry>, String> {
+    let conn = state.lock()?;
+    load_time_entries(&conn).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn add_time_entry(state: State<'_, DatabaseConnection>, mut entry: TimeEntry) -> Result<Vec<TimeEntry>, String> {
+    let conn = state.lock()?;
+    auto_round_entry(&conn, &mut entry).map_err(|e| e.to_string())?;
+    insert_time_entry(&conn, &entry).map_err(|e| e.to_string())?;
+    load_time_entries(&conn).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn add_time_entry_returning_id(state: State<'_, DatabaseConnection>, mut entry: TimeEntry) -> Result<i64, String> {
+    let conn = state.lock()?;
+    auto_round_entry(&conn, &mut entry).map_err(|e| e.to_string())?;
+    insert_time_entry(&conn, &entry).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn update_time_entry(state: State<'_, DatabaseConnection>, mut entry: TimeEntry) -> Result<Vec<TimeEntry>, String> {
+    let conn = state.lock()?;
+    auto_round_entry(&conn, &mut entry).map_err(|e| e.to_string())?;
+
+    conn.execute(
+        "UPDATE time_entries SET task = ?1, start_time = ?2, end_time = ?3, duration = ?4, category = ?5, subject_id = ?6, task_id = ?7, description = ?8 WHERE id = ?9",
+        rusqlite::params![
+            &entry.task,
+            &entry.start_time,
+            &entry.end_time,
+            entry.duration,
+            &entry.category,
+            entry.subject_id,
+            entry.task_id,
+            &entry.description,
+            entry.id
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+
+    load_time_entries(&conn).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn delete_time_entry(state: State<'_, DatabaseConnection>, id: i64) -> Result<Vec<TimeEntry>, String> {
+    let conn = state.lock()?;
+
+    conn.execute("DELETE FROM time_entries WHERE id = ?1", rusqlite::params![id])
+        .map_err(|e| e.to_string())?;
+
+    load_time_entries(&conn).map_err(|e| e.to_string())
+}
+
+// Hides an entry from the active list without losing it; returns the active entries
+#[tauri::command]
+fn archive_time_entry(state: State<'_, DatabaseConnection>, id: i64) -> Result<Vec<TimeEntry>, String> {
+    let conn = state.lock()?;
+
+    let changed = conn
+        .execute("UPDATE time_entries SET archived = 1 WHERE id = ?1", rusqlite::params![id])
+        .map_err(|e| e.to_string())?;
+    if changed == 0 {
+        return Err("Time entry not found".to_string());
+    }
+
+    load_time_entries(&conn).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn get_archived_time_entries(state: State<'_, DatabaseConnection>) -> Result<Vec<TimeEntry>, String> {
+    let conn = state.lock()?;
+    select_time_entries(&conn, "WHERE archived = 1").map_err(|e| e.to_string())
+}
+
+// Renames (or merges) an exact-match category across `table`; `table` is trusted, never user input
+fn rename_category_in(conn: &Connection, table: &str, from: &str, to: &str) -> Result<u64, String> {
+    let to = to.trim();
+    if to.is_empty() {
+        return Err("Target category cannot be empty".to_string());
+    }
+
+    let changed = conn
+        .execute(
+            &format!("UPDATE {} SET category = ?1 WHERE category = ?2", table),
+            rusqlite::params![to, from],
+        )
+        .map_err(|e| e.to_string())?;
+
+    Ok(changed as u64)
+}
+
+// Distinct non-blank categories used in `table`, sorted case-insensitively; `table` is trusted
+fn load_distinct_categories(conn: &Connection, table: &str) -> SqliteResult<Vec<String>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT DISTINCT category FROM {} WHERE TRIM(category) != '' ORDER BY category COLLATE NOCASE, category",
+        table
+    ))?;
+    let categories = stmt.query_map([], |row| row.get(0))?;
+    categories.collect()
+}
+
+#[tauri::command]
+fn get_time_categories(state: State<'_, DatabaseConnection>) -> Result<Vec<String>, String> {
+    let conn = state.lock()?;
+    load_distinct_categories(&conn, "time_entries").map_err(|e| e.to_string())
+}
+
+// Merges one category into another, e.g. after deciding "job" and "work" are the same
+#[tauri::command]
+fn recategorize_time_entries(state: State<'_, DatabaseConnection>, from: String, to: String) -> Result<u64, String> {
+    let conn = state.lock()?;
+    rename_category_in(&conn, "time_entries", &from, &to)
+}
+
+#[tauri::command]
+fn rename_time_category(state: State<'_, DatabaseConnection>, old: String, new: String) -> Result<u64, String> {
+    let conn = state.lock()?;
+    rename_category_in(&conn, "time_entries", &old, &new)
+}
+
+#[tauri::command]
+fn get_time_per_task(state: State<'_, DatabaseConnection>, include_archived: Option<bool>) -> Result<Vec<(String, i64)>, String> {
+    let conn = state.lock()?;
+
+    // Running timers (no end_time yet) are excluded; archived entries only when asked for
+    let mut stmt = conn
+        .prepare(
+            "SELECT task, SUM(duration) AS total FROM time_entries
+             WHERE end_time IS NOT NULL AND (?1 OR archived = 0)
+             GROUP BY task
+             ORDER BY total DESC",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let totals = stmt
+        .query_map(rusqlite::params![include_archived.unwrap_or(false)], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map_err(|e| e.to_string())?;
+
+    totals.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())
+}
+
+// Finished entries starting on the given local day (YYYY-MM-DD). Running timers are left out,
+// as in get_time_per_task, since their duration isn't known yet.
+fn load_day_time_log(conn: &Connection, date: &str) -> Result<DayTimeLog, String> {
+    let day = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map_err(|_| format!("Invalid date '{}', expected YYYY-MM-DD", date))?;
+
+    // start_time is UTC, so compare on the parsed local date rather than the stored string
+    let entries: Vec<TimeEntry> = load_time_entries(conn)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter(|entry| entry.end_time.is_some())
+        .filter(|entry| {
+            DateTime::parse_from_rfc3339(&entry.start_time)
+                .map(|start| start.with_timezone(&Local).date_naive() == day)
+                .unwrap_or(false)
+        })
+        .collect();
+
+    let total_duration = entries.iter().map(|entry| entry.duration).sum();
+
+    Ok(DayTimeLog {
+        date: day.format("%Y-%m-%d").to_string(),
+        entries,
+        total_duration,
+    })
+}
+
+#[tauri::command]
+fn get_daily_time_log(state: State<'_, DatabaseConnection>, date: String) -> Result<DayTimeLog, String> {
+    let conn = state.lock()?;
+    load_day_time_log(&conn, &date)
+}
+
+// Tracked time for a local day against the daily_time_goal setting, counted the same way as
+// get_daily_time_log
+#[tauri::command]
+fn get_daily_goal_progress(state: State<'_, DatabaseConnection>, date: String) -> Result<GoalProgress, String> {
+    let conn = state.lock()?;
+    let log = load_day_time_log(&conn, &date)?;
+    let goal_minutes = load_daily_time_goal(&conn).map_err(|e| e.to_string())?;
+
+    Ok(GoalProgress {
+        date: log.date,
+        tracked_seconds: log.total_duration,
+        goal_minutes,
+        percent: goal_minutes.map(|minutes| log.total_duration as f64 / (minutes as f64 * 60.0) * 100.0),
+    })
+}
+
+#[tauri::command]
+fn get_estimate_accuracy(state: State<'_, DatabaseConnection>, task_id: u64) -> Result<EstimateComparison, String> {
+    let conn = state.lock()?;
+    let task_id = task_id as i64;
+
+    let estimated_minutes: Option<i64> = conn
+        .query_row(
+            "SELECT estimated_minutes FROM tasks WHERE id = ?1",
+            rusqlite::params![task_id],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Task not found".to_string())?;
+
+    // Only finished entries count; durations are stored in seconds
+    let (entry_count, tracked_seconds): (i64, i64) = conn
+        .query_row(
+            "SELECT COUNT(*), COALESCE(SUM(duration), 0) FROM time_entries
+             WHERE task_id = ?1 AND end_time IS NOT NULL",
+            rusqlite::params![task_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map_err(|e| e.to_string())?;
+
+    let actual_minutes = (entry_count > 0).then_some(tracked_seconds / 60);
+    let delta_minutes = estimated_minutes.zip(actual_minutes).map(|(estimated, actual)| actual - estimated);
+
+    Ok(EstimateComparison {
+        task_id,
+        estimated_minutes,
+        actual_minutes,
+        delta_minutes,
+    })
+}
+
+// Opt-in: entries are rounded up to this many minutes whenever they are saved finished
+const TIME_ROUNDING_KEY: &str = "time_rounding_minutes";
+
+fn load_time_rounding(conn: &Connection) -> SqliteResult<Option<u32>> {
+    Ok(read_setting(conn, TIME_ROUNDING_KEY)?
+        .and_then(|value| value.parse::<u32>().ok())
+        .filter(|minutes| *minutes > 0))
+}
+
+// Round up to the next interval boundary; an empty entry bills one full interval
+fn round_up_to_interval(duration_secs: i64, interval_minutes: u32) -> i64 {
+    let interval_secs = i64::from(interval_minutes) * 60;
+    if duration_secs <= 0 {
+        interval_secs
+    } else {
+        (duration_secs + interval_secs - 1) / interval_secs * interval_secs
+    }
+}
+
+// The rounded duration and the end time that goes with it; None when the start is unreadable
+fn rounded_span(start_time: &str, duration_secs: i64, interval_minutes: u32) -> Option<(i64, String)> {
+    let rounded = round_up_to_interval(duration_secs, interval_minutes);
+    let start = DateTime::parse_from_rfc3339(start_time).ok()?;
+    let end_time = start
+        .checked_add_signed(chrono::Duration::try_seconds(rounded)?)?
+        .with_timezone(&Utc)
+        .to_rfc3339_opts(SecondsFormat::Millis, true);
+    Some((rounded, end_time))
+}
+
+// Applies the time_rounding_minutes setting to a finished entry. Running entries, and ones whose
+// start can't be read, are saved as given.
+fn auto_round_entry(conn: &Connection, entry: &mut TimeEntry) -> SqliteResult<()> {
+    if entry.end_time.is_none() {
+        return Ok(());
+    }
+    if let Some((duration, end_time)) = load_time_rounding(conn)?
+        .and_then(|interval| rounded_span(&entry.start_time, entry.duration, interval))
+    {
+        entry.duration = duration;
+        entry.end_time = Some(end_time);
+    }
+    Ok(())
+}
+
+#[tauri::command]
+fn get_time_rounding(state: State<'_, DatabaseConnection>) -> Result<Option<u32>, String> {
+    let conn = state.lock()?;
+    load_time_rounding(&conn).map_err(|e| e.to_string())
+}
+
+// None or 0 turns automatic rounding off
+#[tauri::command]
+fn set_time_rounding(state: State<'_, DatabaseConnection>, minutes: Option<u32>) -> Result<(), String> {
+    let value = match minutes {
+        Some(minutes) if minutes > 0 => minutes.to_string(),
+        _ => String::new(),
+    };
+
+    let conn = state.lock()?;
+    write_setting(&conn, TIME_ROUNDING_KEY, &value).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn round_duration(state: State<'_, DatabaseConnection>, id: i64, interval_minutes: u32) -> Result<TimeEntry, String> {
+    if interval_minutes == 0 {
+        return Err("Interval must be at least one minute".to_string());
+    }
+
+    let conn = state.lock()?;
+    let entry = load_time_entry(&conn, id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Time entry not found".to_string())?;
+
+    let (rounded, end_time) = rounded_span(&entry.start_time, entry.duration, interval_minutes)
+        .ok_or_else(|| "Time entry has an invalid start time".to_string())?;
+
+    conn.execute(
+        "UPDATE time_entries SET duration = ?1, end_time = ?2 WHERE id = ?3",
+        rusqlite::params![rounded, &end_time, id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    load_time_entry(&conn, id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Time entry not found".to_string())
+}
+
+// Moves an entry to `new_date` (YYYY-MM-DD) at the same local time of day, keeping its length
+#[tauri::command]
+fn reschedule_time_entry(state: State<'_, DatabaseConnection>, id: i64, new_date: String) -> Result<TimeEntry, String> {
+    let date = NaiveDate::parse_from_str(&new_date, "%Y-%m-%d")
+        .map_err(|_| format!("Invalid date '{}', expected YYYY-MM-DD", new_date))?;
+
+    let conn = state.lock()?;
+    let entry = load_time_entry(&conn, id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Time entry not found".to_string())?;
+
+    let start = DateTime::parse_from_rfc3339(&entry.start_time)
+        .map_err(|_| "Time entry has an invalid start time".to_string())?
+        .with_timezone(&Utc);
+    let length = match entry.end_time.as_deref() {
+        Some(end_time) => Some(
+            DateTime::parse_from_rfc3339(end_time)
+                .map_err(|_| "Time entry has an invalid end time".to_string())?
+                .with_timezone(&Utc)
+                - start,
+        ),
+        None => None,
+    };
+
+    let new_start = reminders::resolve_local_datetime(&Local, date.and_time(start.with_timezone(&Local).time()))
+        .ok_or_else(|| "Cannot place the entry on that date".to_string())?;
+    let format = |dt: DateTime<Utc>| dt.to_rfc3339_opts(SecondsFormat::Millis, true);
+
+    // Open entries only move their start; the running timer keeps counting from there
+    conn.execute(
+        "UPDATE time_entries SET start_time = ?1, end_time = ?2 WHERE id = ?3",
+        rusqlite::params![format(new_start), length.map(|length| format(new_start + length)), id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    load_time_entry(&conn, id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Time entry not found".to_string())
+}
+
+#[tauri::command]
+fn split_time_entry(state: State<'_, DatabaseConnection>, id: i64, at: String) -> Result<Vec<TimeEntry>, String> {
+    let mut conn = state.lock()?;
+    let entry = load_time_entry(&conn, id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Time entry not found".to_string())?;
+
+    let parse = |raw: &str, what: &str| {
+        DateTime::parse_from_rfc3339(raw)
+            .map(|dt| dt.with_timezone(&Utc))
+            .map_err(|_| format!("Invalid {} '{}'", what, raw))
+    };
+    let start = parse(&entry.start_time, "start time")?;
+    let end = match entry.end_time.as_deref() {
+        Some(end_time) => parse(end_time, "end time")?,
+        None => return Err("Cannot split a running time entry".to_string()),
+    };
+    let split_at = parse(&at, "split time")?;
+
+    if split_at <= start || split_at >= end {
+        return Err("Split time must fall strictly inside the entry".to_string());
+    }
+
+    let split_time = split_at.to_rfc3339_opts(SecondsFormat::Millis, true);
+    let second = TimeEntry {
+        id: 0,
+        start_time: split_time.clone(),
+        duration: (end - split_at).num_seconds(),
+        ..entry.clone()
+    };
+
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    tx.execute(
+        "UPDATE time_entries SET end_time = ?1, duration = ?2 WHERE id = ?3",
+        rusqlite::params![&split_time, (split_at - start).num_seconds(), id],
+    )
+    .map_err(|e| e.to_string())?;
+    insert_time_entry(&tx, &second).map_err(|e| e.to_string())?;
+    tx.commit().map_err(|e| e.to_string())?;
+
+    load_time_entries(&conn).map_err(|e| e.to_string())
+}
+
+// Recovery for timers left running, e.g. by a crash: stops every open entry at `end_time`
+#[tauri::command]
+fn close_open_timers(state: State<'_, DatabaseConnection>, end_time: String) -> Result<ClosedTimers, String> {
+    let end = DateTime::parse_from_rfc3339(&end_time)
+        .map_err(|_| format!("Invalid end time '{}'", end_time))?
+        .with_timezone(&Utc);
+    let end_str = end.to_rfc3339_opts(SecondsFormat::Millis, true);
+
+    let mut conn = state.lock()?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+
+    let open = select_time_entries(&tx, "WHERE end_time IS NULL").map_err(|e| e.to_string())?;
+    let mut result = ClosedTimers { closed: Vec::new(), skipped: Vec::new() };
+
+    for entry in open {
+        let start = DateTime::parse_from_rfc3339(&entry.start_time).map(|start| start.with_timezone(&Utc));
+        let duration = match start {
+            Ok(start) if start <= end => (end - start).num_seconds(),
+            _ => {
+                result.skipped.push(entry);
+                continue;
+            }
+        };
+
+        let mut closed = TimeEntry {
+            end_time: Some(end_str.clone()),
+            duration,
+            ..entry
+        };
+        auto_round_entry(&tx, &mut closed).map_err(|e| e.to_string())?;
+
+        tx.execute(
+            "UPDATE time_entries SET end_time = ?1, duration = ?2 WHERE id = ?3",
+            rusqlite::params![&closed.end_time, closed.duration, closed.id],
+        )
+        .map_err(|e| e.to_string())?;
+
+        result.closed.push(closed);
+    }
+
+    tx.commit().map_err(|e| e.to_string())?;
+
+    Ok(result)
+}
+
+// Spending Commands
+const EXPENSE_COLUMNS: &str = "id, amount, description, category, date, expense_type";
+
+fn expense_from_row(row: &rusqlite::Row) -> SqliteResult<Expense> {
+    Ok(Expense {
+        id: row.get(0)?,
+        amount: row.get(1)?,
+        description: row.get(2)?,
+        category: row.get(3)?,
+        date: row.get(4)?,
+        expense_type: row.get(5)?,
+    })
+}
+
+fn load_expenses(conn: &Connection) -> SqliteResult<Vec<Expense>> {
+    // id follows insertion order (AUTOINCREMENT, same as created_at) and keeps same-date rows stable
+    let mut stmt = conn.prepare(&format!("SELECT {} FROM expenses ORDER BY date DESC, id DESC", EXPENSE_COLUMNS))?;
+
+    let expenses = stmt.query_map([], expense_from_row)?;
+
+    expenses.collect()
+}
+
+fn insert_expense(conn: &Connection, expense: &Expense) -> SqliteResult<i64> {
+    // An explicit category always wins over the keyword rules
+    let category = if expense.category.trim().is_empty() {
+        categorize_expense(conn, &expense.description)?
+    } else {
+        expense.category.clone()
+    };
+
+    conn.execute(
+        "INSERT INTO expenses (amount, description, category, date, expense_type) VALUES (?1, ?2, ?3, ?4, ?5)",
+        rusqlite::params![
+            expense.amount,
+            &expense.description,
+            &category,
+            &expense.date,
+            &expense.expense_type
+        ],
+    )?;
+
+    Ok(conn.last_insert_rowid())
+}
+
+#[tauri::command]
+fn import_expenses_csv(state: State<'_, DatabaseConnection>, path: String, mapping: import::ColumnMapping) -> Result<Vec<Expense>, String> {
+    let contents = std::fs::read_to_string(&path).map_err(|e| format!("Cannot read '{}': {}", path, e))?;
+    let expenses = import::parse_expenses_csv(&contents, &mapping)?;
+
+    let mut conn = state.lock()?;
+
+    // All rows or none: a parse error above or an insert error here leaves the table untouched
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    for expense in &expenses {
+        insert_expense(&tx, expense).map_err(|e| e.to_string())?;
+    }
+    tx.commit().map_err(|e| e.to_string())?;
+
+    load_expenses(&conn).map_err(|e| e.to_string())
+}
+
+// Largest expenses (income excluded), optionally within an inclusive YYYY-MM-DD range
+#[tauri::command]
+fn get_top_expenses(state: State<'_, DatabaseConnection>, limit: u32, start: Option<String>, end: Option<String>) -> Result<Vec<Expense>, String> {
+    let conn = state.lock()?;
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM expenses
+             WHERE expense_type = 'expense'
+             AND (?1 IS NULL OR date(date) >= date(?1))
+             AND (?2 IS NULL OR date(date) <= date(?2))
+             ORDER BY amount DESC, id DESC
+             LIMIT ?3",
+            EXPENSE_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let expenses = stmt
+        .query_map(rusqlite::params![start, end, limit], expense_from_row)
+        .map_err(|e| e.to_string())?;
+
+    expenses.collect::<SqliteResult<Vec<_>>>().map_err(|e| e.to_string())
+}
+
+// Expense rows in one category for a month, with their total, for drilling into a category
+#[tauri::command]
+fn get_category_detail(state: State<'_, DatabaseConnection>, category: String, year: i32, month: u32) -> Result<CategoryDetail, String> {
+    if !(1..=12).contains(&month) {
+        return Err("Month must be between 1 and 12".to_string());
+    }
+
+    let conn = state.lock()?;
+    let month_key = format!("{:04}-{:02}", year, month);
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM expenses
+             WHERE expense_type = 'expense' AND category = ?1 AND strftime('%Y-%m', date) = ?2
+             ORDER BY date DESC, id DESC",
+            EXPENSE_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let expenses = stmt
+        .query_map(rusqlite::params![&category, &month_key], expense_from_row)
+        .map_err(|e| e.to_string())?
+        .collect::<SqliteResult<Vec<_>>>()
+        .map_err(|e| e.to_string())?;
+
+    let total = expenses.iter().map(|expense| expense.amount).sum();
+
+    Ok(CategoryDetail {
+        category,
+        expenses,
+        total,
+    })
+}
+
+// Per-category spending figures for [start, end] (YYYY-MM-DD); income is left out, and so are
+// categories with nothing spent in the range
+#[tauri::command]
+fn get_category_stats(state: State<'_, DatabaseConnection>, start: String, end: String) -> Result<Vec<CategoryStat>, String> {
+    let (start_date, end_date) = parse_date_range(&start, &end)?;
+
+    let conn = state.lock()?;
+    let mut stmt = conn
+        .prepare(
+            "SELECT category, COUNT(*), SUM(amount), AVG(amount), MIN(amount), MAX(amount) FROM expenses
+             WHERE expense_type = 'expense' AND date(date) >= ?1 AND date(date) <= ?2
+             GROUP BY category
+             ORDER BY SUM(amount) DESC, category",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let stats = stmt
+        .query_map(
+            rusqlite::params![start_date.format("%Y-%m-%d").to_string(), end_date.format("%Y-%m-%d").to_string()],
+            |row| {
+                Ok(CategoryStat {
+                    category: row.get(0)?,
+                    count: row.get::<_, i64>(1)? as u64,
+                    total: row.get(2)?,
+                    average: row.get(3)?,
+                    min: row.get(4)?,
+                    max: row.get(5)?,
+                })
+            },
+        )
+        .map_err(|e| e.to_string())?;
+
+    stats.collect::<SqliteResult<Vec<_>>>().map_err(|e| e.to_string())
+}
+
+// Returns the number of expenses renamed. Keyword rules follow the rename so new imports don't
+// bring the old name back, and so does the budget unless the new name already has one.
+#[tauri::command]
+fn rename_expense_category(state: State<'_, DatabaseConnection>, old: String, new: String) -> Result<u64, String> {
+    let mut conn = state.lock()?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+
+    let changed = rename_category_in(&tx, "expenses", &old, &new)?;
+    rename_category_in(&tx, "category_rules", &old, &new)?;
+    tx.execute(
+        "UPDATE OR IGNORE category_budgets SET category = ?1 WHERE category = ?2",
+        rusqlite::params![new.trim(), &old],
+    )
+    .map_err(|e| e.to_string())?;
+
+    tx.commit().map_err(|e| e.to_string())?;
+
+    Ok(changed)
+}
+
+#[tauri::command]
+fn get_expense_categories(state: State<'_, DatabaseConnection>) -> Result<Vec<String>, String> {
+    let conn = state.lock()?;
+    load_distinct_categories(&conn, "expenses").map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn get_expenses(state: State<'_, DatabaseConnection>) -> Result<Vec<Expense>, String> {
+    let conn = state.lock()?;
+    load_expenses(&conn).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn add_expense(app: AppHandle, state: State<'_, DatabaseConnection>, expense: Expense) -> Result<AddExpenseResult, String> {
+    let conn = state.lock()?;
+    let id = insert_expense(&conn, &expense).map_err(|e| e.to_string())?;
+
+    // Advisory only: the expense is stored either way
+    let budget_warning = check_category_budget(&conn, id).map_err(|e| e.to_string())?;
+    if let Some(ref warning) = budget_warning {
+        notify_budget_exceeded(&app, warning);
+    }
+
+    Ok(AddExpenseResult {
+        expenses: load_expenses(&conn).map_err(|e| e.to_string())?,
+        budget_warning,
+    })
+}
+
+#[tauri::command]
+fn add_expense_returning_id(state: State<'_, DatabaseConnection>, expense: Expense) -> Result<i64, String> {
+    let conn = state.lock()?;
+    insert_expense(&conn, &expense).map_err(|e| e.to_string())
+}
+
+// Re-checks the budget like add_expense, since an edit can raise the amount or move category
+#[tauri::command]
+fn update_expense(app: AppHandle, state: State<'_, DatabaseConnection>, expense: Expense) -> Result<AddExpenseResult, String> {
+    let conn = state.lock()?;
+
+    conn.execute(
+        "UPDATE expenses SET amount = ?1, description = ?2, category = ?3, date = ?4, expense_type = ?5 WHERE id = ?6",
+        rusqlite::params![
+            expense.amount,
+            &expense.description,
+            &expense.category,
+            &expense.date,
+            &expense.expense_type,
+            expense.id
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+
+    let budget_warning = check_category_budget(&conn, expense.id).map_err(|e| e.to_string())?;
+    if let Some(ref warning) = budget_warning {
+        notify_budget_exceeded(&app, warning);
+    }
+
+    Ok(AddExpenseResult {
+        expenses: load_expenses(&conn).map_err(|e| e.to_string())?,
+        budget_warning,
+    })
+}
+
+#[tauri::command]
+fn delete_expense(state: State<'_, DatabaseConnection>, id: i64) -> Result<Vec<Expense>, String> {
+    let conn = state.lock()?;
+
+    conn.execute("DELETE FROM expenses WHERE id = ?1", rusqlite::params![id])
+        .map_err(|e| e.to_string())?;
+
+    load_expenses(&conn).map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// Expense Category Rules
+// ============================================================================
+
+fn load_category_rules(conn: &Connection) -> SqliteResult<Vec<CategoryRule>> {
+    let mut stmt = conn.prepare("SELECT id, keyword, category FROM category_rules ORDER BY id")?;
+
+    let rules = stmt.query_map([], |row| {
+        Ok(CategoryRule {
+            id: row.get(0)?,
+            keyword: row.get(1)?,
+            category: row.get(2)?,
+        })
+    })?;
+
+    rules.collect()
+}
+
+// First rule whose keyword appears in the description wins (case-insensitive)
+fn categorize_expense(conn: &Connection, description: &str) -> SqliteResult<String> {
+    let description = description.to_lowercase();
+    let category = load_category_rules(conn)?
+        .into_iter()
+        .find(|rule| description.contains(&rule.keyword.to_lowercase()))
+        .map(|rule| rule.category)
+        .unwrap_or_else(|| "Uncategorized".to_string());
+
+    Ok(category)
+}
+
+#[tauri::command]
+fn get_category_rules(state: State<'_, DatabaseConnection>) -> Result<Vec<CategoryRule>, String> {
+    let conn = state.lock()?;
+    load_category_rules(&conn).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn add_category_rule(state: State<'_, DatabaseConnection>, keyword: String, category: String) -> Result<Vec<CategoryRule>, String> {
+    let conn = state.lock()?;
+
+    let keyword = keyword.trim();
+    let category = category.trim();
+    if keyword.is_empty() || category.is_empty() {
+        return Err("Keyword and category are required".to_string());
+    }
+
+    conn.execute(
+        "INSERT INTO category_rules (keyword, category) VALUES (?1, ?2)",
+        rusqlite::params![keyword, category],
+    )
+    .map_err(|e| e.to_string())?;
+
+    load_category_rules(&conn).map_err(|e| e.to_string())
+}
+
+// Inclusive [start, end] day range from "YYYY-MM-DD" (or longer ISO) strings
+fn parse_date_range(start: &str, end: &str) -> Result<(NaiveDate, NaiveDate), String> {
+    let parse_date = |raw: &str| {
+        NaiveDate::parse_from_str(raw.get(..10).unwrap_or(raw), "%Y-%m-%d")
+            .map_err(|_| format!("Invalid date '{}', expected YYYY-MM-DD", raw))
+    };
+
+    let start_date = parse_date(start)?;
+    let end_date = parse_date(end)?;
+    if end_date < start_date {
+        return Err("End date must not be before start date".to_string());
+    }
+    Ok((start_date, end_date))
+}
+
+// ============================================================================
+// Completion Reports
+// ============================================================================
+
+#[tauri::command]
+fn get_completions_per_day(state: State<'_, DatabaseConnection>, start: String, end: String) -> Result<Vec<(String, u64)>, String> {
+    let (start_date, end_date) = parse_date_range(&start, &end)?;
+
+    let conn = state.lock()?;
+
+    // completed_at is stored in UTC; bucket by the local calendar day
+    let mut stmt = conn
+        .prepare(
+            "SELECT date(completed_at, 'localtime') AS day, COUNT(*) FROM tasks
+             WHERE completed_at IS NOT NULL AND date(completed_at, 'localtime') BETWEEN ?1 AND ?2
+             GROUP BY day",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let counts = stmt
+        .query_map(
+            rusqlite::params![start_date.format("%Y-%m-%d").to_string(), end_date.format("%Y-%m-%d").to_string()],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? as u64)),
+        )
+        .map_err(|e| e.to_string())?
+        .collect::<SqliteResult<std::collections::HashMap<_, _>>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(start_date
+        .iter_days()
+        .take_while(|day| *day <= end_date)
+        .map(|day| {
+            let key = day.format("%Y-%m-%d").to_string();
+            let count = counts.get(&key).copied().unwrap_or(0);
+            (key, count)
+        })
+        .collect())
+}
+
+// All-time completions per local weekday, Monday first, with empty days as 0
+#[tauri::command]
+fn get_completions_by_weekday(state: State<'_, DatabaseConnection>) -> Result<Vec<(String, u64)>, String> {
+    let conn = state.lock()?;
+
+    // completed_at is UTC; strftime('%w') numbers Sunday as 0
+    let mut stmt = conn
+        .prepare(
+            "SELECT CAST(strftime('%w', completed_at, 'localtime') AS INTEGER) AS weekday, COUNT(*) FROM tasks
+             WHERE completed_at IS NOT NULL
+             GROUP BY weekday",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let counts = stmt
+        .query_map([], |row| Ok((row.get::<_, Option<i64>>(0)?, row.get::<_, i64>(1)? as u64)))
+        .map_err(|e| e.to_string())?
+        .collect::<SqliteResult<Vec<_>>>()
+        .map_err(|e| e.to_string())?;
+
+    let mut by_weekday = [0u64; 7];
+    for (weekday, count) in counts {
+        if let Some(weekday) = weekday.filter(|day| (0..7).contains(day)) {
+            // Shift so Monday is 0
+            by_weekday[((weekday + 6) % 7) as usize] += count;
+        }
+    }
+
+    Ok(["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"]
+        .iter()
+        .zip(by_weekday)
+        .map(|(day, count)| (day.to_string(), count))
+        .collect())
+}
+
+// Mean hours from creation to completion over Done tasks, optionally for one priority; 0 when
+// there are none. Tasks whose created_at equals completed_at are left out: legacy Done tasks were
+// backfilled that way, and tasks created already Done have no cycle to measure.
+#[tauri::command]
+fn get_average_cycle_time(state: State<'_, DatabaseConnection>, priority: Option<String>) -> Result<f64, String> {
+    let conn = state.lock()?;
+
+    let average: Option<f64> = conn
+        .query_row(
+            "SELECT AVG((julianday(completed_at) - julianday(created_at)) * 24) FROM tasks
+             WHERE status = 'Done' AND created_at IS NOT NULL AND completed_at IS NOT NULL
+               AND julianday(created_at) != julianday(completed_at)
+               AND (?1 IS NULL OR priority = ?1)",
+            rusqlite::params![priority],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+
+    Ok(average.unwrap_or(0.0))
+}
+
+// Velocity is measured over the last 14 local calendar days, today included
+const VELOCITY_WINDOW_DAYS: i64 = 14;
+
+// Projects the local date (YYYY-MM-DD) by which `remaining` tasks would be done at the recent
+// completion rate. None when nothing was completed in the window, since there is no rate to use.
+#[tauri::command]
+fn estimate_completion(state: State<'_, DatabaseConnection>, remaining: u32) -> Result<Option<String>, String> {
+    let today = Local::now().date_naive();
+    if remaining == 0 {
+        return Ok(Some(today.format("%Y-%m-%d").to_string()));
+    }
+
+    let window_start = today - chrono::Duration::days(VELOCITY_WINDOW_DAYS - 1);
+
+    let conn = state.lock()?;
+
+    // Same local-day bucketing as get_completions_per_day
+    let completed: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM tasks
+             WHERE completed_at IS NOT NULL AND date(completed_at, 'localtime') BETWEEN ?1 AND ?2",
+            rusqlite::params![window_start.format("%Y-%m-%d").to_string(), today.format("%Y-%m-%d").to_string()],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+
+    if completed == 0 {
+        return Ok(None);
+    }
+
+    let per_day = completed as f64 / VELOCITY_WINDOW_DAYS as f64;
+    let days_needed = (remaining as f64 / per_day).ceil() as i64;
+
+    Ok(today
+        .checked_add_signed(chrono::Duration::days(days_needed))
+        .map(|date| date.format("%Y-%m-%d").to_string()))
+}
+
+// ============================================================================
+// Spending Reports
+// ============================================================================
+
+// (expenses_total, income_total) keyed by year-month, for months from `since_month` on
+fn load_monthly_money_totals(conn: &Connection, since_month: &str) -> SqliteResult<std::collections::HashMap<String, (f64, f64)>> {
+    let mut stmt = conn.prepare(
+        "SELECT strftime('%Y-%m', date) AS month,
+                COALESCE(SUM(CASE WHEN expense_type = 'expense' THEN amount ELSE 0 END), 0),
+                COALESCE(SUM(CASE WHEN expense_type = 'income' THEN amount ELSE 0 END), 0)
+         FROM expenses
+         WHERE strftime('%Y-%m', date) >= ?1
+         GROUP BY month",
+    )?;
+
+    let totals = stmt.query_map(rusqlite::params![since_month], |row| {
+        Ok((row.get::<_, String>(0)?, (row.get(1)?, row.get(2)?)))
+    })?;
+
+    totals.collect()
+}
+
+#[tauri::command]
+fn get_monthly_expense_trend(state: State<'_, DatabaseConnection>, months: u32) -> Result<Vec<(String, f64, f64)>, String> {
+    let conn = state.lock()?;
+
+    // Build the month keys backwards from this month, then flip to ascending
+    let today = Local::now().date_naive();
+    let (mut year, mut month) = (today.year(), today.month());
+    let mut keys: Vec<String> = Vec::new();
+    for _ in 0..months {
+        keys.push(format!("{:04}-{:02}", year, month));
+        if month == 1 {
+            month = 12;
+            year -= 1;
+        } else {
+            month -= 1;
+        }
+    }
+    keys.reverse();
+
+    let first_key = match keys.first() {
+        Some(key) => key.clone(),
+        None => return Ok(Vec::new()),
+    };
+
+    let totals = load_monthly_money_totals(&conn, &first_key).map_err(|e| e.to_string())?;
+
+    Ok(keys
+        .into_iter()
+        .map(|key| {
+            let (expenses, income) = totals.get(&key).copied().unwrap_or((0.0, 0.0));
+            (key, expenses, income)
+        })
+        .collect())
+}
+
+// Expense totals per category for one year-month
+fn load_monthly_spend_by_category(conn: &Connection, month_key: &str) -> SqliteResult<std::collections::HashMap<String, f64>> {
+    let mut stmt = conn.prepare(
+        "SELECT category, SUM(amount) FROM expenses
+         WHERE expense_type = 'expense' AND strftime('%Y-%m', date) = ?1
+         GROUP BY category",
+    )?;
+
+    let totals = stmt.query_map(rusqlite::params![month_key], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    totals.collect()
+}
+
+// Each category spent on in the month or the one before, largest increase first
+#[tauri::command]
+fn get_category_mom_delta(state: State<'_, DatabaseConnection>, year: i32, month: u32) -> Result<Vec<CategoryDelta>, String> {
+    if !(1..=12).contains(&month) {
+        return Err("Month must be between 1 and 12".to_string());
+    }
+    let (previous_year, previous_month) = if month == 1 { (year - 1, 12) } else { (year, month - 1) };
+
+    let conn = state.lock()?;
+    let current = load_monthly_spend_by_category(&conn, &format!("{:04}-{:02}", year, month)).map_err(|e| e.to_string())?;
+    let previous = load_monthly_spend_by_category(&conn, &format!("{:04}-{:02}", previous_year, previous_month))
+        .map_err(|e| e.to_string())?;
+
+    let categories: std::collections::BTreeSet<&String> = current.keys().chain(previous.keys()).collect();
+    let mut deltas: Vec<CategoryDelta> = categories
+        .into_iter()
+        .map(|category| {
+            let this_month = current.get(category).copied().unwrap_or(0.0);
+            let last_month = previous.get(category).copied().unwrap_or(0.0);
+            CategoryDelta {
+                category: category.clone(),
+                this_month,
+                last_month,
+                difference: this_month - last_month,
+                percent_change: (last_month > 0.0).then(|| (this_month - last_month) / last_month * 100.0),
+            }
+        })
+        .collect();
+
+    deltas.sort_by(|a, b| b.difference.partial_cmp(&a.difference).unwrap_or(std::cmp::Ordering::Equal));
+    Ok(deltas)
+}
+
+// (income - expense) / income for the month as a percentage; 0 when there is no income
+#[tauri::command]
+fn get_savings_rate(state: State<'_, DatabaseConnection>, year: i32, month: u32) -> Result<f64, String> {
+    if !(1..=12).contains(&month) {
+        return Err("Month must be between 1 and 12".to_string());
+    }
+
+    let conn = state.lock()?;
+    let month_key = format!("{:04}-{:02}", year, month);
+
+    let (expenses, income) = load_monthly_money_totals(&conn, &month_key)
+        .map_err(|e| e.to_string())?
+        .get(&month_key)
+        .copied()
+        .unwrap_or((0.0, 0.0));
+
+    if income <= 0.0 {
+        return Ok(0.0);
+    }
+
+    Ok((income - expenses) / income * 100.0)
+}
+
+// Spending over the last `days` days including today, divided by `days`, so days without any
+// spending still count
+fn load_average_daily_spend(conn: &Connection, days: i64) -> Result<f64, String> {
+    if days <= 0 {
+        return Err("Days must be positive".to_string());
+    }
+
+    let today = Local::now().date_naive();
+    let first_day = today - chrono::Duration::days(days - 1);
+
+    let total: f64 = conn
+        .query_row(
+            "SELECT COALESCE(SUM(amount), 0) FROM expenses
+             WHERE expense_type = 'expense' AND date(date) >= ?1 AND date(date) <= ?2",
+            rusqlite::params![first_day.format("%Y-%m-%d").to_string(), today.format("%Y-%m-%d").to_string()],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+
+    Ok(total / days as f64)
+}
+
+#[tauri::command]
+fn get_average_daily_spend(state: State<'_, DatabaseConnection>, days: i64) -> Result<f64, String> {
+    let conn = state.lock()?;
+    load_average_daily_spend(&conn, days)
+}
+
+// The average over the last `days` days, projected over the whole current month
+#[tauri::command]
+fn get_projected_month_spend(state: State<'_, DatabaseConnection>, days: i64) -> Result<f64, String> {
+    let conn = state.lock()?;
+    let average = load_average_daily_spend(&conn, days)?;
+
+    let today = Local::now().date_naive();
+    Ok(average * days_in_month(today.year(), today.month()) as f64)
+}
+
+// ============================================================================
+// Last Active View
+// ============================================================================
+
+// Route names from the frontend router, so the app can reopen where it was left
+const LAST_VIEW_KEY: &str = "last_view";
+const VIEWS: [&str; 6] = ["tasks", "calendar", "time", "spend", "semester", "about"];
+
+#[tauri::command]
+fn get_last_view(state: State<'_, DatabaseConnection>) -> Result<String, String> {
+    let conn = state.lock()?;
+
+    let view = read_setting(&conn, LAST_VIEW_KEY)
+        .map_err(|e| e.to_string())?
+        .filter(|view| VIEWS.contains(&view.as_str()))
+        .unwrap_or_else(|| "tasks".to_string());
+
+    Ok(view)
+}
+
+#[tauri::command]
+fn set_last_view(state: State<'_, DatabaseConnection>, view: String) -> Result<(), String> {
+    if !VIEWS.contains(&view.as_str()) {
+        return Err(format!("Unknown view '{}', expected one of {}", view, VIEWS.join(", ")));
+    }
+
+    let conn = state.lock()?;
+    write_setting(&conn, LAST_VIEW_KEY, &view).map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// Week Boundaries
+// ============================================================================
+
+// 0 = Sunday ... 6 = Saturday, matching JavaScript's Date.getDay()
+const WEEK_START_DAY_KEY: &str = "week_start_day";
+const DEFAULT_WEEK_START_DAY: u32 = 1; // Monday, as in ISO weeks
+
+fn read_week_start_day(conn: &Connection) -> SqliteResult<u32> {
+    Ok(read_setting(conn, WEEK_START_DAY_KEY)?
+        .and_then(|value| value.parse::<u32>().ok())
+        .filter(|day| *day <= 6)
+        .unwrap_or(DEFAULT_WEEK_START_DAY))
+}
+
+// First day of the week containing `date`
+fn week_start(date: NaiveDate, week_start_day: u32) -> NaiveDate {
+    let offset = (date.weekday().num_days_from_sunday() + 7 - week_start_day) % 7;
+    date - chrono::Duration::days(offset as i64)
+}
+
+#[tauri::command]
+fn get_week_start_day(state: State<'_, DatabaseConnection>) -> Result<u32, String> {
+    let conn = state.lock()?;
+    read_week_start_day(&conn).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn set_week_start_day(state: State<'_, DatabaseConnection>, day: u32) -> Result<(), String> {
+    if day > 6 {
+        return Err("Week start day must be between 0 (Sunday) and 6 (Saturday)".to_string());
+    }
+
+    let conn = state.lock()?;
+    write_setting(&conn, WEEK_START_DAY_KEY, &day.to_string()).map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// Cash Flow
+// ============================================================================
+
+// First day of the bucket containing `date`
+fn cashflow_bucket_start(date: NaiveDate, bucket: &str, week_start_day: u32) -> NaiveDate {
+    match bucket {
+        "week" => week_start(date, week_start_day),
+        "month" => date.with_day(1).expect("day 1 is always valid"),
+        _ => date,
+    }
+}
+
+fn next_cashflow_bucket(start: NaiveDate, bucket: &str) -> Option<NaiveDate> {
+    match bucket {
+        "week" => start.checked_add_days(chrono::Days::new(7)),
+        "month" => start.checked_add_months(chrono::Months::new(1)),
+        _ => start.succ_opt(),
+    }
+}
+
+#[tauri::command]
+fn get_cashflow_series(state: State<'_, DatabaseConnection>, start: String, end: String, bucket: String) -> Result<Vec<CashflowPoint>, String> {
+    if !["day", "week", "month"].contains(&bucket.as_str()) {
+        return Err(format!("Unknown bucket '{}', expected day, week or month", bucket));
+    }
+
+    let (start_date, end_date) = parse_date_range(&start, &end)?;
+
+    let conn = state.lock()?;
+    let week_start_day = read_week_start_day(&conn).map_err(|e| e.to_string())?;
+
+    // Daily sums from SQL, folded into the requested buckets below
+    let mut stmt = conn
+        .prepare(
+            "SELECT strftime('%Y-%m-%d', date) AS day,
+                    COALESCE(SUM(CASE WHEN expense_type = 'income' THEN amount ELSE 0 END), 0),
+                    COALESCE(SUM(CASE WHEN expense_type = 'expense' THEN amount ELSE 0 END), 0)
+             FROM expenses
+             WHERE date(date) >= ?1 AND date(date) <= ?2
+             GROUP BY day",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let daily = stmt
+        .query_map(
+            rusqlite::params![start_date.format("%Y-%m-%d").to_string(), end_date.format("%Y-%m-%d").to_string()],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, f64>(1)?, row.get::<_, f64>(2)?)),
+        )
+        .map_err(|e| e.to_string())?
+        .collect::<SqliteResult<Vec<_>>>()
+        .map_err(|e| e.to_string())?;
+
+    let mut totals: std::collections::HashMap<NaiveDate, (f64, f64)> = std::collections::HashMap::new();
+    for (day, income, expense) in daily {
+        if let Ok(date) = NaiveDate::parse_from_str(&day, "%Y-%m-%d") {
+            let entry = totals.entry(cashflow_bucket_start(date, &bucket, week_start_day)).or_insert((0.0, 0.0));
+            entry.0 += income;
+            entry.1 += expense;
+        }
+    }
+
+    // Zero-filled buckets; the balance accumulates from the start of the range
+    let label_format = if bucket == "month" { "%Y-%m" } else { "%Y-%m-%d" };
+    let mut series = Vec::new();
+    let mut balance = 0.0;
+    let mut cursor = Some(cashflow_bucket_start(start_date, &bucket, week_start_day));
+    while let Some(period_start) = cursor.filter(|date| *date <= end_date) {
+        let (income, expense) = totals.get(&period_start).copied().unwrap_or((0.0, 0.0));
+        balance += income - expense;
+        series.push(CashflowPoint {
+            period: period_start.format(label_format).to_string(),
+            income,
+            expense,
+            balance,
+        });
+        cursor = next_cashflow_bucket(period_start, &bucket);
+    }
+
+    Ok(series)
+}
+
+// Net balance (all income minus all expenses) at the end of each day in [start, end], opening
+// from everything recorded before `start`. Days are bucketed as in get_cashflow_series.
+#[tauri::command]
+fn get_balance_timeline(state: State<'_, DatabaseConnection>, start: String, end: String) -> Result<Vec<(String, f64)>, String> {
+    let (start_date, end_date) = parse_date_range(&start, &end)?;
+    let start_key = start_date.format("%Y-%m-%d").to_string();
+    let end_key = end_date.format("%Y-%m-%d").to_string();
+
+    let conn = state.lock()?;
+
+    let net = "COALESCE(SUM(CASE WHEN expense_type = 'income' THEN amount WHEN expense_type = 'expense' THEN -amount ELSE 0 END), 0)";
+
+    let opening: f64 = conn
+        .query_row(
+            &format!("SELECT {} FROM expenses WHERE date(date) < ?1", net),
+            rusqlite::params![&start_key],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT strftime('%Y-%m-%d', date) AS day, {} FROM expenses
+             WHERE date(date) >= ?1 AND date(date) <= ?2
+             GROUP BY day",
+            net
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let deltas = stmt
+        .query_map(rusqlite::params![&start_key, &end_key], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, f64>(1)?))
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<SqliteResult<std::collections::HashMap<_, _>>>()
+        .map_err(|e| e.to_string())?;
+
+    // Days without transactions carry the previous balance forward
+    let mut balance = opening;
+    Ok(start_date
+        .iter_days()
+        .take_while(|day| *day <= end_date)
+        .map(|day| {
+            let key = day.format("%Y-%m-%d").to_string();
+            balance += deltas.get(&key).copied().unwrap_or(0.0);
+            (key, balance)
+        })
+        .collect())
+}
+
+// ============================================================================
+// Budget Period
+// ============================================================================
+
+const BUDGET_ANCHOR_DAY_KEY: &str = "budget_period_anchor_day";
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    let (next_year, next_month) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+    NaiveDate::from_ymd_opt(next_year, next_month, 1)
+        .and_then(|first_of_next| first_of_next.pred_opt())
+        .map(|last| last.day())
+        .unwrap_or(28)
+}
+
+// The anchor day in the given month, clamped to the month's last day (e.g. 31 -> Feb 28)
+fn budget_anchor_date(year: i32, month: u32, anchor_day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, anchor_day.clamp(1, days_in_month(year, month)))
+        .expect("clamped day is always valid")
+}
+
+// [start, end) of the budget period containing `today`
+fn current_budget_period(today: NaiveDate, anchor_day: u32) -> (NaiveDate, NaiveDate) {
+    let shift = |year: i32, month: u32, delta: i32| {
+        let index = year * 12 + month as i32 - 1 + delta;
+        (index.div_euclid(12), index.rem_euclid(12) as u32 + 1)
+    };
+
+    let this_anchor = budget_anchor_date(today.year(), today.month(), anchor_day);
+    let (start_year, start_month) = if today >= this_anchor {
+        (today.year(), today.month())
+    } else {
+        shift(today.year(), today.month(), -1)
+    };
+    let (end_year, end_month) = shift(start_year, start_month, 1);
+
+    (
+        budget_anchor_date(start_year, start_month, anchor_day),
+        budget_anchor_date(end_year, end_month, anchor_day),
+    )
+}
+
+#[tauri::command]
+fn set_budget_anchor_day(state: State<'_, DatabaseConnection>, day: u32) -> Result<(), String> {
+    if !(1..=31).contains(&day) {
+        return Err("Anchor day must be between 1 and 31".to_string());
+    }
+
+    let conn = state.lock()?;
+    write_setting(&conn, BUDGET_ANCHOR_DAY_KEY, &day.to_string()).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn get_current_period_spending(state: State<'_, DatabaseConnection>) -> Result<f64, String> {
+    let conn = state.lock()?;
+
+    // Defaults to calendar months when no anchor day is configured
+    let anchor_day = read_setting(&conn, BUDGET_ANCHOR_DAY_KEY)
+        .map_err(|e| e.to_string())?
+        .and_then(|value| value.parse::<u32>().ok())
+        .unwrap_or(1);
+
+    let (start, end) = current_budget_period(Local::now().date_naive(), anchor_day);
+
+    conn.query_row(
+        "SELECT COALESCE(SUM(amount), 0) FROM expenses
+         WHERE expense_type = 'expense' AND date(date) >= ?1 AND date(date) < ?2",
+        rusqlite::params![start.format("%Y-%m-%d").to_string(), end.format("%Y-%m-%d").to_string()],
+        |row| row.get(0),
+    )
+    .map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// Category Budgets
+// ============================================================================
+
+fn load_category_budgets(conn: &Connection) -> SqliteResult<Vec<CategoryBudget>> {
+    let mut stmt = conn.prepare("SELECT category, monthly_limit FROM category_budgets ORDER BY category")?;
+
+    let budgets = stmt.query_map([], |row| {
+        Ok(CategoryBudget {
+            category: row.get(0)?,
+            monthly_limit: row.get(1)?,
+        })
+    })?;
+
+    budgets.collect()
+}
+
+#[tauri::command]
+fn get_category_budgets(state: State<'_, DatabaseConnection>) -> Result<Vec<CategoryBudget>, String> {
+    let conn = state.lock()?;
+    load_category_budgets(&conn).map_err(|e| e.to_string())
+}
+
+// A limit of zero or less removes the category's budget
+#[tauri::command]
+fn set_category_budget(state: State<'_, DatabaseConnection>, category: String, monthly_limit: f64) -> Result<(), String> {
+    let category = category.trim();
+    if category.is_empty() {
+        return Err("Category cannot be empty".to_string());
+    }
+
+    let conn = state.lock()?;
+
+    if monthly_limit <= 0.0 {
+        conn.execute("DELETE FROM category_budgets WHERE category = ?1", rusqlite::params![category])
+            .map_err(|e| e.to_string())?;
+    } else {
+        conn.execute(
+            "INSERT OR REPLACE INTO category_budgets (category, monthly_limit, updated_at) VALUES (?1, ?2, datetime('now'))",
+            rusqlite::params![category, monthly_limit],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
+
+// Whether the month of the given expense is now over its category's budget
+fn check_category_budget(conn: &Connection, expense_id: i64) -> SqliteResult<Option<BudgetWarning>> {
+    let row: Option<(String, f64)> = conn
+        .query_row(
+            "SELECT e.category, b.monthly_limit FROM expenses e
+             INNER JOIN category_budgets b ON b.category = e.category
+             WHERE e.id = ?1 AND e.expense_type = 'expense'",
+            rusqlite::params![expense_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()?;
+
+    let (category, monthly_limit) = match row {
+        Some(row) => row,
+        None => return Ok(None),
+    };
+
+    let spent: f64 = conn.query_row(
+        "SELECT COALESCE(SUM(amount), 0) FROM expenses
+         WHERE expense_type = 'expense' AND category = ?1
+         AND strftime('%Y-%m', date) = (SELECT strftime('%Y-%m', date) FROM expenses WHERE id = ?2)",
+        rusqlite::params![&category, expense_id],
+        |row| row.get(0),
+    )?;
+
+    if spent <= monthly_limit {
+        return Ok(None);
+    }
+
+    Ok(Some(BudgetWarning {
+        category,
+        monthly_limit,
+        spent,
+        overage: spent - monthly_limit,
+    }))
+}
+
+fn notify_budget_exceeded(app: &AppHandle, warning: &BudgetWarning) {
+    let result = app
+        .notification()
+        .builder()
+        .title(format!("{} budget exceeded", warning.category))
+        .body(format!(
+            "{:.2} of {:.2} spent this month ({:.2} over)",
+            warning.spent, warning.monthly_limit, warning.overage
+        ))
+        .show();
+
+    if let Err(err) = result {
+        eprintln!("budget notification failed: {}", err);
+    }
+}
+
+// ============================================================================
+// Monthly PDF Report
+// ============================================================================
+
+fn load_monthly_report(conn: &Connection, year: i32, month: u32, include_archived: bool) -> SqliteResult<report::MonthlyReport> {
+    let month_key = format!("{:04}-{:02}", year, month);
+
+    // Tasks completed before completed_at existed fall back to their due month
+    let mut task_stmt = conn.prepare(
+        "SELECT title FROM tasks
+         WHERE status = 'Done' AND strftime('%Y-%m', COALESCE(datetime(completed_at, 'localtime'), due_date)) = ?1
+         ORDER BY COALESCE(completed_at, due_date)",
+    )?;
+    let completed_tasks = task_stmt
+        .query_map(rusqlite::params![&month_key], |row| row.get(0))?
+        .collect::<SqliteResult<Vec<String>>>()?;
+
+    let mut time_stmt = conn.prepare(
+        "SELECT category, SUM(duration) AS total FROM time_entries
+         WHERE end_time IS NOT NULL AND strftime('%Y-%m', start_time) = ?1 AND (?2 OR archived = 0)
+         GROUP BY category
+         ORDER BY total DESC",
+    )?;
+    let hours_by_category = time_stmt
+        .query_map(rusqlite::params![&month_key, include_archived], |row| {
+            let seconds: i64 = row.get(1)?;
+            Ok((row.get::<_, String>(0)?, seconds as f64 / 3600.0))
+        })?
+        .collect::<SqliteResult<Vec<_>>>()?;
+
+    let (expense_total, income_total) = load_monthly_money_totals(conn, &month_key)?
+        .get(&month_key)
+        .copied()
+        .unwrap_or((0.0, 0.0));
+
+    Ok(report::MonthlyReport {
+        year,
+        month,
+        completed_tasks,
+        hours_by_category,
+        expense_total,
+        income_total,
+    })
+}
+
+#[tauri::command]
+fn export_monthly_report_pdf(
+    state: State<'_, DatabaseConnection>,
+    year: i32,
+    month: u32,
+    path: String,
+    include_archived: Option<bool>,
+) -> Result<(), String> {
+    if !(1..=12).contains(&month) {
+        return Err("Month must be between 1 and 12".to_string());
+    }
+
+    let monthly_report = {
+        let conn = state.lock()?;
+        load_monthly_report(&conn, year, month, include_archived.unwrap_or(false)).map_err(|e| e.to_string())?
+    };
+
+    report::write_monthly_report_pdf(&monthly_report, &path)
+}
+
+// ============================================================================
+// Subject Commands
+// ============================================================================
+
+fn load_subjects(conn: &Connection) -> SqliteResult<Vec<Subject>> {
+    let mut stmt = conn.prepare("SELECT id, name, color, semester, credits FROM subjects ORDER BY name")?;
+
+    let subjects = stmt.query_map([], |row| {
+        Ok(Subject {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            color: row.get(2)?,
+            semester: row.get(3)?,
+            credits: row.get(4)?,
+        })
+    })?;
+
+    subjects.collect()
+}
+
+#[tauri::command]
+fn get_subjects(state: State<'_, DatabaseConnection>) -> Result<Vec<Subject>, String> {
+    let conn = state.lock()?;
+    load_subjects(&conn).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn add_subject(state: State<'_, DatabaseConnection>, subject: Subject) -> Result<Vec<Subject>, String> {
+    let conn = state.lock()?;
+
+    conn.execute(
+        "INSERT INTO subjects (name, color, semester, credits) VALUES (?1, ?2, ?3, ?4)",
+        rusqlite::params![&subject.name, &subject.color, &subject.semester, subject.credits],
+    )
+    .map_err(|e| e.to_string())?;
+
+    drop(conn);
+    let state_clone = state.clone();
+    get_subjects(state_clone)
+}
+
+#[tauri::command]
+fn update_subject(state: State<'_, DatabaseConnection>, subject: Subject) -> Result<Vec<Subject>, String> {
+    let conn = state.lock()?;
+
+    conn.execute(
+        "UPDATE subjects SET name = ?1, color = ?2, semester = ?3, credits = ?4 WHERE id = ?5",
+        rusqlite::params![&subject.name, &subject.color, &subject.semester, subject.credits, subject.id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    drop(conn);
+    get_subjects(state)
+}
+
+#[tauri::command]
+fn delete_subject(state: State<'_, DatabaseConnection>, id: i64) -> Result<Vec<Subject>, String> {
+    let conn = state.lock()?;
+
+    conn.execute("DELETE FROM subjects WHERE id = ?1", rusqlite::params![id])
         .map_err(|e| e.to_string())?;
 
     drop(conn);
@@ -680,32 +3377,32 @@ fn delete_subject(state: State<'_, DatabaseConnection>, id: i64) -> Result<Vec<S
 // Exam Commands
 // ============================================================================
 
-#[tauri::command]
-fn get_exams(state: State<'_, DatabaseConnection>) -> Result<Vec<Exam>, String> {
-    let conn = state.0.lock().map_err(|e| e.to_string())?;
-    let mut stmt = conn
-        .prepare("SELECT id, subject_id, title, exam_date, weight, notes FROM exams ORDER BY exam_date")
-        .map_err(|e| e.to_string())?;
+fn load_exams(conn: &Connection) -> SqliteResult<Vec<Exam>> {
+    let mut stmt = conn.prepare("SELECT id, subject_id, title, exam_date, weight, notes FROM exams ORDER BY exam_date")?;
 
-    let exams = stmt
-        .query_map([], |row| {
-            Ok(Exam {
-                id: row.get(0)?,
-                subject_id: row.get(1)?,
-                title: row.get(2)?,
-                exam_date: row.get(3)?,
-                weight: row.get(4)?,
-                notes: row.get(5)?,
-            })
+    let exams = stmt.query_map([], |row| {
+        Ok(Exam {
+            id: row.get(0)?,
+            subject_id: row.get(1)?,
+            title: row.get(2)?,
+            exam_date: row.get(3)?,
+            weight: row.get(4)?,
+            notes: row.get(5)?,
         })
-        .map_err(|e| e.to_string())?;
+    })?;
 
-    exams.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())
+    exams.collect()
+}
+
+#[tauri::command]
+fn get_exams(state: State<'_, DatabaseConnection>) -> Result<Vec<Exam>, String> {
+    let conn = state.lock()?;
+    load_exams(&conn).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
 fn add_exam(state: State<'_, DatabaseConnection>, exam: Exam) -> Result<Vec<Exam>, String> {
-    let conn = state.0.lock().map_err(|e| e.to_string())?;
+    let conn = state.lock()?;
 
     conn.execute(
         "INSERT INTO exams (subject_id, title, exam_date, weight, notes) VALUES (?1, ?2, ?3, ?4, ?5)",
@@ -719,7 +3416,7 @@ fn add_exam(state: State<'_, DatabaseConnection>, exam: Exam) -> Result<Vec<Exam
 
 #[tauri::command]
 fn update_exam(state: State<'_, DatabaseConnection>, exam: Exam) -> Result<Vec<Exam>, String> {
-    let conn = state.0.lock().map_err(|e| e.to_string())?;
+    let conn = state.lock()?;
 
     conn.execute(
         "UPDATE exams SET subject_id = ?1, title = ?2, exam_date = ?3, weight = ?4, notes = ?5 WHERE id = ?6",
@@ -733,7 +3430,7 @@ fn update_exam(state: State<'_, DatabaseConnection>, exam: Exam) -> Result<Vec<E
 
 #[tauri::command]
 fn delete_exam(state: State<'_, DatabaseConnection>, id: i64) -> Result<Vec<Exam>, String> {
-    let conn = state.0.lock().map_err(|e| e.to_string())?;
+    let conn = state.lock()?;
 
     conn.execute("DELETE FROM exams WHERE id = ?1", rusqlite::params![id])
         .map_err(|e| e.to_string())?;
@@ -747,8 +3444,8 @@ fn delete_exam(state: State<'_, DatabaseConnection>, id: i64) -> Result<Vec<Exam
 // ============================================================================
 
 #[tauri::command]
-fn get_next_focus_item(state: State<'_, DatabaseConnection>) -> Result<FocusItem, String> {
-    let conn = state.0.lock().map_err(|e| e.to_string())?;
+fn get_next_focus_item(state: State<'_, DatabaseConnection>, include_archived: Option<bool>) -> Result<FocusItem, String> {
+    let conn = state.lock()?;
     
     // Load active tasks with subject info
     let tasks = load_tasks(&conn).map_err(|e| e.to_string())?;
@@ -783,12 +3480,12 @@ fn get_next_focus_item(state: State<'_, DatabaseConnection>) -> Result<FocusItem
         .collect::<Result<Vec<_>, _>>()
         .map_err(|e| e.to_string())?;
     
-    // Load time entries for effort calculation
+    // Load time entries for effort calculation; archived entries only when asked for
     let mut time_stmt = conn
-        .prepare("SELECT task, SUM(duration) as total FROM time_entries GROUP BY task")
+        .prepare("SELECT task, SUM(duration) as total FROM time_entries WHERE ?1 OR archived = 0 GROUP BY task")
         .map_err(|e| e.to_string())?;
     let time_by_task: std::collections::HashMap<String, i64> = time_stmt
-        .query_map([], |row| {
+        .query_map(rusqlite::params![include_archived.unwrap_or(false)], |row| {
             let task: String = row.get(0)?;
             let total: i64 = row.get(1)?;
             Ok((task, total))
@@ -933,103 +3630,1010 @@ fn get_next_focus_item(state: State<'_, DatabaseConnection>) -> Result<FocusItem
 }
 
 // ============================================================================
-// Weekly Summary (Time Reality Check)
+// Weekly Summary (Time Reality Check)
+// ============================================================================
+
+#[tauri::command]
+fn get_weekly_summary(state: State<'_, DatabaseConnection>, include_archived: Option<bool>) -> Result<WeeklySummary, String> {
+    let conn = state.lock()?;
+    
+    let today = Local::now().date_naive();
+    let week_start_day = read_week_start_day(&conn).map_err(|e| e.to_string())?;
+    let this_week_start = week_start(today, week_start_day);
+    let days_since_week_start = (today - this_week_start).num_days();
+    let week_start_str = this_week_start.format("%Y-%m-%d").to_string();
+    
+    // Get planned minutes from tasks due this week
+    let planned_minutes: i64 = conn
+        .query_row(
+            "SELECT COALESCE(SUM(COALESCE(estimated_minutes, 60)), 0) FROM tasks 
+             WHERE status != 'Done' AND due_date >= ?1 AND due_date <= date('now', '+7 days')",
+            rusqlite::params![&week_start_str],
+            |row| row.get(0),
+        )
+        .unwrap_or(0);
+    
+    // Get actual minutes tracked this week; archived entries only when asked for
+    let actual_minutes: i64 = conn
+        .query_row(
+            "SELECT COALESCE(SUM(duration), 0) / 60 FROM time_entries WHERE start_time >= ?1 AND (?2 OR archived = 0)",
+            rusqlite::params![&week_start_str, include_archived.unwrap_or(false)],
+            |row| row.get(0),
+        )
+        .unwrap_or(0);
+    
+    let completion_rate = if planned_minutes > 0 {
+        ((actual_minutes as f64) / (planned_minutes as f64) * 100.0).min(100.0)
+    } else {
+        100.0
+    };
+    
+    let days_into_week = days_since_week_start + 1;
+    let expected_progress = (days_into_week as f64 / 7.0) * 100.0;
+    let on_track = completion_rate >= expected_progress * 0.8;
+    
+    let pace_warning = if planned_minutes > 0 && completion_rate < 50.0 && days_into_week >= 3 {
+        let remaining = planned_minutes - actual_minutes;
+        let days_left = 7 - days_into_week;
+        if days_left > 0 {
+            Some(format!("At this pace, you need {} min/day to catch up.", remaining / days_left))
+        } else {
+            None
+        }
+    } else {
+        None
+    };
+    
+    Ok(WeeklySummary {
+        planned_minutes,
+        actual_minutes,
+        completion_rate,
+        on_track,
+        pace_warning,
+    })
+}
+
+// ============================================================================
+// Encryption
+// ============================================================================
+
+#[tauri::command]
+fn is_database_locked() -> Result<bool, String> {
+    Ok(encryption::is_locked())
+}
+
+#[tauri::command]
+fn unlock_database(state: State<'_, DatabaseConnection>, passphrase: String) -> Result<(), String> {
+    let path = encryption::locked_path().ok_or_else(|| "ZenTrack is not locked".to_string())?;
+
+    let unlocked = match init_database(path, Some(&passphrase)) {
+        Ok(conn) => conn,
+        Err(err) if encryption::is_not_a_database(&err) => return Err("Incorrect passphrase".to_string()),
+        Err(err) => return Err(err.to_string()),
+    };
+
+    let mut conn = state.0.lock().map_err(|e| e.to_string())?;
+    *conn = unlocked;
+    encryption::set_locked(None);
+    Ok(())
+}
+
+// Encrypts the active database with `passphrase`, changes it, or with an empty one removes
+// encryption. The new file is written beside the old one and only swapped in once complete.
+#[tauri::command]
+fn set_encryption(app: AppHandle, state: State<'_, DatabaseConnection>, passphrase: String) -> Result<(), String> {
+    let mut conn = state.lock()?;
+    let db_path = conn
+        .path()
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+        .ok_or_else(|| "The active database has no file to encrypt".to_string())?;
+
+    let rekeyed_path = encryption::export_rekeyed(&conn, &db_path, &passphrase)?;
+
+    // The old file has to be closed before it can be replaced. If closing fails, it stays active
+    let placeholder = Connection::open_in_memory().map_err(|e| e.to_string())?;
+    let original = std::mem::replace(&mut *conn, placeholder);
+    if let Err((original, err)) = original.close() {
+        *conn = original;
+        let _ = std::fs::remove_file(&rekeyed_path);
+        return Err(err.to_string());
+    }
+
+    let new_key = Some(passphrase.as_str()).filter(|passphrase| !passphrase.is_empty());
+    let swapped = encryption::replace_database_file(&rekeyed_path, &db_path)
+        .and_then(|_| init_database(db_path.clone(), new_key).map_err(|e| e.to_string()));
+
+    match swapped {
+        Ok(new_conn) => {
+            *conn = new_conn;
+            Ok(())
+        }
+        Err(err) => {
+            // Whichever file is in place now, never leave commands writing to the placeholder:
+            // a plain one reopens as is, an encrypted one locks until its passphrase is entered
+            match init_database(db_path.clone(), None) {
+                Ok(reopened) => *conn = reopened,
+                Err(_) => {
+                    encryption::set_locked(Some(db_path));
+                    let _ = app.emit(encryption::LOCKED_EVENT, ());
+                }
+            }
+            Err(err)
+        }
+    }
+}
+
+// ============================================================================
+// App Settings
+// ============================================================================
+
+fn read_setting(conn: &Connection, key: &str) -> SqliteResult<Option<String>> {
+    conn.query_row(
+        "SELECT value FROM app_settings WHERE key = ?1",
+        rusqlite::params![key],
+        |row| row.get(0),
+    )
+    .optional()
+}
+
+fn write_setting(conn: &Connection, key: &str, value: &str) -> SqliteResult<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO app_settings (key, value, updated_at) VALUES (?1, ?2, datetime('now'))",
+        rusqlite::params![key, value],
+    )?;
+    Ok(())
+}
+
+#[tauri::command]
+fn get_app_setting(state: State<'_, DatabaseConnection>, key: String) -> Result<Option<String>, String> {
+    let conn = state.lock()?;
+    read_setting(&conn, &key).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn set_app_setting(state: State<'_, DatabaseConnection>, key: String, value: String) -> Result<(), String> {
+    let conn = state.lock()?;
+    write_setting(&conn, &key, &value).map_err(|e| e.to_string())
+}
+
+// None when the daily digest is off
+#[tauri::command]
+fn get_daily_digest_time(state: State<'_, DatabaseConnection>) -> Result<Option<String>, String> {
+    let conn = state.lock()?;
+    Ok(read_setting(&conn, reminders::DAILY_DIGEST_TIME_KEY)
+        .map_err(|e| e.to_string())?
+        .filter(|time| !time.is_empty()))
+}
+
+// `time` is local "HH:MM"; an empty string turns the digest off
+#[tauri::command]
+fn set_daily_digest_time(state: State<'_, DatabaseConnection>, time: String) -> Result<(), String> {
+    let time = time.trim();
+    if !time.is_empty() && chrono::NaiveTime::parse_from_str(time, "%H:%M").is_err() {
+        return Err(format!("Invalid time '{}', expected HH:MM", time));
+    }
+
+    let conn = state.lock()?;
+    write_setting(&conn, reminders::DAILY_DIGEST_TIME_KEY, time).map_err(|e| e.to_string())
+}
+
+const DAILY_TIME_GOAL_KEY: &str = "daily_time_goal";
+
+// Minutes; None when unset, cleared or unreadable
+fn load_daily_time_goal(conn: &Connection) -> SqliteResult<Option<u32>> {
+    Ok(read_setting(conn, DAILY_TIME_GOAL_KEY)?
+        .and_then(|minutes| minutes.parse::<u32>().ok())
+        .filter(|minutes| *minutes > 0))
+}
+
+#[tauri::command]
+fn get_daily_time_goal(state: State<'_, DatabaseConnection>) -> Result<Option<u32>, String> {
+    let conn = state.lock()?;
+    load_daily_time_goal(&conn).map_err(|e| e.to_string())
+}
+
+// None or 0 clears the goal
+#[tauri::command]
+fn set_daily_time_goal(state: State<'_, DatabaseConnection>, minutes: Option<u32>) -> Result<(), String> {
+    let value = match minutes {
+        Some(minutes) if minutes > 0 => minutes.to_string(),
+        _ => String::new(),
+    };
+
+    let conn = state.lock()?;
+    write_setting(&conn, DAILY_TIME_GOAL_KEY, &value).map_err(|e| e.to_string())
+}
+
+const DEFAULT_TASK_PRIORITY_KEY: &str = "default_task_priority";
+const DEFAULT_TASK_STATUS_KEY: &str = "default_task_status";
+const TASK_PRIORITIES: [&str; 3] = ["Low", "Medium", "High"];
+const TASK_STATUSES: [&str; 3] = ["Pending", "In Progress", "Done"];
+
+// (priority, status) for tasks created without them; unset or unknown values fall back to
+// Medium and Pending
+fn load_task_defaults(conn: &Connection) -> SqliteResult<(String, String)> {
+    let priority = read_setting(conn, DEFAULT_TASK_PRIORITY_KEY)?
+        .filter(|priority| TASK_PRIORITIES.contains(&priority.as_str()))
+        .unwrap_or_else(|| "Medium".to_string());
+    let status = read_setting(conn, DEFAULT_TASK_STATUS_KEY)?
+        .filter(|status| TASK_STATUSES.contains(&status.as_str()))
+        .unwrap_or_else(|| "Pending".to_string());
+
+    Ok((priority, status))
+}
+
+const THEME_KEY: &str = "theme";
+const THEMES: [&str; 3] = ["light", "dark", "system"];
+
+#[tauri::command]
+fn get_theme(state: State<'_, DatabaseConnection>) -> Result<String, String> {
+    let conn = state.lock()?;
+
+    let theme = read_setting(&conn, THEME_KEY)
+        .map_err(|e| e.to_string())?
+        .filter(|theme| THEMES.contains(&theme.as_str()))
+        .unwrap_or_else(|| "system".to_string());
+
+    Ok(theme)
+}
+
+#[tauri::command]
+fn set_theme(state: State<'_, DatabaseConnection>, theme: String) -> Result<(), String> {
+    if !THEMES.contains(&theme.as_str()) {
+        return Err(format!("Unknown theme '{}', expected light, dark or system", theme));
+    }
+
+    let conn = state.lock()?;
+    write_setting(&conn, THEME_KEY, &theme).map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// Profiles
+// ============================================================================
+
+// Extra profiles live next to the main database as profiles/<name>.db; "default" is the main one
+const DEFAULT_PROFILE: &str = "default";
+const PROFILES_DIR: &str = "profiles";
+
+fn profiles_dir() -> Result<PathBuf, String> {
+    let mut dir = get_db_path()?;
+    dir.pop();
+    dir.push(PROFILES_DIR);
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Cannot create profiles directory '{}': {}", dir.display(), e))?;
+    Ok(dir)
+}
+
+fn profile_path(name: &str) -> Result<PathBuf, String> {
+    if name == DEFAULT_PROFILE {
+        return get_db_path();
+    }
+
+    // Names become file names, so keep them to a safe character set
+    let valid = !name.is_empty()
+        && name.len() <= 64
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err("Profile names may only use letters, digits, '-' and '_' (up to 64)".to_string());
+    }
+
+    let mut path = profiles_dir()?;
+    path.push(format!("{}.db", name));
+    Ok(path)
+}
+
+// Copies the current database into a new, unencrypted profile, leaving the active one unchanged.
+// This goes through sqlcipher_export because SQLCipher refuses the backup API for encrypted files.
+#[tauri::command]
+fn create_profile(state: State<'_, DatabaseConnection>, name: String) -> Result<(), String> {
+    let path = profile_path(&name)?;
+    if path.exists() {
+        return Err(format!("A profile named '{}' already exists", name));
+    }
+
+    let conn = state.lock()?;
+    let copy_path = encryption::export_rekeyed(&conn, &path, "")
+        .map_err(|e| format!("Cannot create profile '{}': {}", name, e))?;
+    std::fs::rename(&copy_path, &path).map_err(|e| format!("Cannot create profile '{}': {}", name, e))
+}
+
+// Swaps the shared connection in place, so the reminder worker (which holds the same handle)
+// uses the new profile from its next pass
+#[tauri::command]
+fn switch_profile(app: AppHandle, state: State<'_, DatabaseConnection>, name: String) -> Result<(), String> {
+    let path = profile_path(&name)?;
+    if !path.exists() {
+        return Err(format!("No profile named '{}'", name));
+    }
+
+    // Switching away from a locked database is allowed, so this takes the mutex directly
+    let mut conn = state.0.lock().map_err(|e| e.to_string())?;
+    // Reminder ids are per database, so the duplicate guard must not carry over
+    reminders::forget_recent_notifications();
+
+    match init_database(path.clone(), None) {
+        Ok(new_conn) => {
+            *conn = new_conn;
+            encryption::set_locked(None);
+            Ok(())
+        }
+        // An encrypted profile: park on a placeholder until unlock_database is given its passphrase
+        Err(err) if encryption::is_not_a_database(&err) => {
+            *conn = Connection::open_in_memory().map_err(|e| e.to_string())?;
+            encryption::set_locked(Some(path));
+            let _ = app.emit(encryption::LOCKED_EVENT, ());
+            Err(encryption::LOCKED_ERROR.to_string())
+        }
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+#[tauri::command]
+fn list_profiles() -> Result<Vec<String>, String> {
+    let mut names: Vec<String> = std::fs::read_dir(profiles_dir()?)
+        .map_err(|e| e.to_string())?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("db") {
+                return None;
+            }
+            path.file_stem().and_then(|stem| stem.to_str()).map(str::to_string)
+        })
+        .filter(|name| name != DEFAULT_PROFILE)
+        .collect();
+    names.sort();
+    names.insert(0, DEFAULT_PROFILE.to_string());
+
+    Ok(names)
+}
+
+#[tauri::command]
+fn get_active_profile(state: State<'_, DatabaseConnection>) -> Result<String, String> {
+    let conn = state.lock()?;
+    let active = conn.path().map(PathBuf::from);
+
+    let profile = list_profiles()?
+        .into_iter()
+        .find(|name| profile_path(name).ok() == active)
+        .unwrap_or_else(|| DEFAULT_PROFILE.to_string());
+
+    Ok(profile)
+}
+
+// ============================================================================
+// Full Backup (Export / Import)
+// ============================================================================
+
+const EXPORT_PROGRESS_EVENT: &str = "export-progress";
+const IMPORT_PROGRESS_EVENT: &str = "import-progress";
+
+#[derive(Serialize, Clone)]
+struct BackupProgress {
+    percent: u8,
+    section: String, // the section about to be processed, or "done"
+}
+
+// Progress is best-effort; a failed emit never aborts the backup
+fn emit_backup_progress(app: &AppHandle, event: &str, step: usize, steps: usize, section: &str) {
+    let percent = (step * 100 / steps.max(1)).min(100) as u8;
+    let _ = app.emit(event, BackupProgress { percent, section: section.to_string() });
+}
+
+fn load_all_task_notes(conn: &Connection) -> SqliteResult<Vec<TaskNote>> {
+    let mut stmt = conn.prepare("SELECT id, task_id, body, created_at FROM task_notes ORDER BY id")?;
+
+    let notes = stmt.query_map([], |row| {
+        Ok(TaskNote {
+            id: row.get(0)?,
+            task_id: row.get(1)?,
+            body: row.get(2)?,
+            created_at: row.get(3)?,
+        })
+    })?;
+
+    notes.collect()
+}
+
+fn load_all_task_history(conn: &Connection) -> SqliteResult<Vec<TaskHistoryEntry>> {
+    let mut stmt = conn.prepare("SELECT id, task_id, field, old_value, new_value, changed_at FROM task_history ORDER BY id")?;
+
+    let entries = stmt.query_map([], |row| {
+        Ok(TaskHistoryEntry {
+            id: row.get(0)?,
+            task_id: row.get(1)?,
+            field: row.get(2)?,
+            old_value: row.get(3)?,
+            new_value: row.get(4)?,
+            changed_at: row.get(5)?,
+        })
+    })?;
+
+    entries.collect()
+}
+
+// Settings that describe this machine rather than the user's data: a backup folder may not exist
+// elsewhere, and digest state would mark the day as already sent. They are neither exported nor
+// restored.
+const MACHINE_LOCAL_SETTINGS: [&str; 4] = [
+    backup::ENABLED_KEY,
+    backup::DIR_KEY,
+    backup::LAST_BACKUP_KEY,
+    reminders::DAILY_DIGEST_LAST_SENT_KEY,
+];
+
+fn is_machine_local_setting(key: &str) -> bool {
+    MACHINE_LOCAL_SETTINGS.contains(&key)
+}
+
+fn load_all_settings(conn: &Connection) -> SqliteResult<Vec<AppSetting>> {
+    let mut stmt = conn.prepare("SELECT key, value FROM app_settings ORDER BY key")?;
+
+    let settings = stmt
+        .query_map([], |row| {
+            Ok(AppSetting {
+                key: row.get(0)?,
+                value: row.get(1)?,
+            })
+        })?
+        .collect::<SqliteResult<Vec<_>>>()?;
+
+    Ok(settings.into_iter().filter(|setting| !is_machine_local_setting(&setting.key)).collect())
+}
+
+// Everything export_all writes; `progress` is called with steps 0-10 before each section
+fn collect_export_data(conn: &Connection, progress: impl Fn(usize, &str)) -> Result<export::ExportData, String> {
+    progress(0, "tasks");
+    let tasks = load_tasks(conn).map_err(|e| e.to_string())?;
+    progress(1, "time_entries");
+    let time_entries = select_time_entries(conn, "").map_err(|e| e.to_string())?;
+    progress(2, "expenses");
+    let expenses = load_expenses(conn).map_err(|e| e.to_string())?;
+    progress(3, "category_rules");
+    let category_rules = load_category_rules(conn).map_err(|e| e.to_string())?;
+    progress(4, "subjects");
+    let subjects = load_subjects(conn).map_err(|e| e.to_string())?;
+    progress(5, "exams");
+    let exams = load_exams(conn).map_err(|e| e.to_string())?;
+    progress(6, "reminders");
+    let reminders = reminders::load_all_reminders(conn).map_err(|e| e.to_string())?;
+    progress(7, "task_notes");
+    let task_notes = load_all_task_notes(conn).map_err(|e| e.to_string())?;
+    progress(8, "task_history");
+    let task_history = load_all_task_history(conn).map_err(|e| e.to_string())?;
+    progress(9, "category_budgets");
+    let category_budgets = load_category_budgets(conn).map_err(|e| e.to_string())?;
+    progress(10, "app_settings");
+    let app_settings = load_all_settings(conn).map_err(|e| e.to_string())?;
+
+    Ok(export::ExportData {
+        tasks,
+        time_entries,
+        expenses,
+        category_rules,
+        subjects,
+        exams,
+        reminders,
+        task_notes,
+        task_history,
+        category_budgets,
+        app_settings,
+    })
+}
+
+// Reads happen under one lock so the snapshot is consistent; serializing and writing the file,
+// the slow part on large datasets, happen after it is released
+#[tauri::command]
+fn export_all(app: AppHandle, state: State<'_, DatabaseConnection>, path: String) -> Result<(), String> {
+    const STEPS: usize = 12;
+    let progress = |step: usize, section: &str| emit_backup_progress(&app, EXPORT_PROGRESS_EVENT, step, STEPS, section);
+
+    let data = {
+        let conn = state.lock()?;
+        collect_export_data(&conn, progress)?
+    };
+
+    progress(11, "writing");
+    export::write_backup(&export::ExportEnvelope::new(SCHEMA_VERSION, data), &path)?;
+    progress(STEPS, "done");
+
+    Ok(())
+}
+
+// Writes an export into the database; the caller holds the transaction. `progress` is called
+// with steps 1-12 before each section, continuing import_all's own steps.
+fn restore_export_data(conn: &Connection, data: &export::ExportData, progress: impl Fn(usize, &str)) -> Result<(), String> {
+    progress(1, "clearing");
+    for table in ["reminders", "task_notes", "task_history", "time_entries", "exams", "subjects", "tasks", "expenses", "category_rules", "category_budgets"] {
+        conn.execute(&format!("DELETE FROM {}", table), []).map_err(|e| e.to_string())?;
+    }
+
+    // Settings are merged rather than cleared, so a backup from before they were exported
+    // keeps the current ones, and machine-local ones are never taken from the file. They go
+    // first because tag validation below reads them.
+    progress(2, "app_settings");
+    for setting in data.app_settings.iter().filter(|setting| !is_machine_local_setting(&setting.key)) {
+        write_setting(conn, &setting.key, &setting.value).map_err(|e| e.to_string())?;
+    }
+
+    // Rows keep their ids so references between them survive the round trip
+    progress(3, "subjects");
+    for subject in &data.subjects {
+        conn.execute(
+            "INSERT INTO subjects (id, name, color, semester, credits) VALUES (?1, ?2, ?3, ?4, ?5)",
+            rusqlite::params![subject.id, &subject.name, &subject.color, &subject.semester, subject.credits],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    progress(4, "tasks");
+    for task in &data.tasks {
+        let tags = validate_tags(conn, &task.tags).map_err(|e| format!("Task '{}': {}", task.title, e))?;
+        let tags_json = serde_json::to_string(&tags).map_err(|e| e.to_string())?;
+        conn.execute(
+            "INSERT INTO tasks (id, title, description, due_date, tags, priority, status, subject_id, estimated_minutes, actual_minutes, is_pinned, created_at, completed_at, status_changed_at, color)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, COALESCE(?12, datetime('now')), CASE WHEN ?7 = 'Done' THEN COALESCE(?15, datetime('now')) END, COALESCE(?13, ?12, datetime('now')), ?14)",
+            rusqlite::params![
+                task.id as i64,
+                &task.title,
+                &task.description,
+                stored_due_date(task.due_date.as_deref()),
+                &tags_json,
+                &task.priority,
+                &task.status,
+                task.subject_id,
+                task.estimated_minutes.unwrap_or(60),
+                task.actual_minutes.unwrap_or(0),
+                task.is_pinned,
+                &task.created_at,
+                &task.status_changed_at,
+                validate_color(task.color.as_deref())?,
+                &task.completed_at
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    progress(5, "exams");
+    for exam in &data.exams {
+        conn.execute(
+            "INSERT INTO exams (id, subject_id, title, exam_date, weight, notes) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            rusqlite::params![exam.id, exam.subject_id, &exam.title, &exam.exam_date, exam.weight, &exam.notes],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    progress(6, "reminders");
+    for reminder in &data.reminders {
+        conn.execute(
+            "INSERT INTO reminders (id, task_id, remind_at, triggered, created_at, acknowledged, delivered_via, delivered_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            rusqlite::params![
+                reminder.id,
+                reminder.task_id,
+                &reminder.remind_at,
+                reminder.triggered,
+                &reminder.created_at,
+                reminder.acknowledged,
+                &reminder.delivered_via,
+                &reminder.delivered_at
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    progress(7, "time_entries");
+    for entry in &data.time_entries {
+        conn.execute(
+            "INSERT INTO time_entries (id, task, start_time, end_time, duration, category, subject_id, task_id, archived, description) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            rusqlite::params![
+                entry.id,
+                &entry.task,
+                &entry.start_time,
+                &entry.end_time,
+                entry.duration,
+                &entry.category,
+                entry.subject_id,
+                entry.task_id,
+                entry.archived,
+                &entry.description
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    progress(8, "expenses");
+    for expense in &data.expenses {
+        conn.execute(
+            "INSERT INTO expenses (id, amount, description, category, date, expense_type) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            rusqlite::params![expense.id, expense.amount, &expense.description, &expense.category, &expense.date, &expense.expense_type],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    progress(9, "category_rules");
+    for rule in &data.category_rules {
+        conn.execute(
+            "INSERT INTO category_rules (id, keyword, category) VALUES (?1, ?2, ?3)",
+            rusqlite::params![rule.id, &rule.keyword, &rule.category],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    progress(10, "task_notes");
+    for note in &data.task_notes {
+        conn.execute(
+            "INSERT INTO task_notes (id, task_id, body, created_at) VALUES (?1, ?2, ?3, ?4)",
+            rusqlite::params![note.id, note.task_id, &note.body, &note.created_at],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    progress(11, "task_history");
+    for entry in &data.task_history {
+        conn.execute(
+            "INSERT INTO task_history (id, task_id, field, old_value, new_value, changed_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            rusqlite::params![entry.id, entry.task_id, &entry.field, &entry.old_value, &entry.new_value, &entry.changed_at],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    progress(12, "category_budgets");
+    for budget in &data.category_budgets {
+        conn.execute(
+            "INSERT INTO category_budgets (category, monthly_limit) VALUES (?1, ?2)",
+            rusqlite::params![&budget.category, budget.monthly_limit],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
+
+// Replaces all exported data with the file's contents in one transaction. The file is read and
+// checked before taking the lock; the lock is then held until commit so the swap is atomic.
+#[tauri::command]
+fn import_all(app: AppHandle, state: State<'_, DatabaseConnection>, path: String) -> Result<(), String> {
+    const STEPS: usize = 13;
+    let progress = |step: usize, section: &str| emit_backup_progress(&app, IMPORT_PROGRESS_EVENT, step, STEPS, section);
+
+    progress(0, "reading");
+    let envelope = export::read_backup(&path, SCHEMA_VERSION)?;
+    let data = envelope.data;
+
+    let mut conn = state.lock()?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+
+    restore_export_data(&tx, &data, progress)?;
+
+    tx.commit().map_err(|e| e.to_string())?;
+    progress(STEPS, "done");
+
+    Ok(())
+}
+
+#[tauri::command]
+fn get_auto_backup(state: State<'_, DatabaseConnection>) -> Result<backup::AutoBackupSettings, String> {
+    let conn = state.lock()?;
+    backup::load_settings(&conn).map_err(|e| e.to_string())
+}
+
+// The backup worker picks up changes on its next check
+#[tauri::command]
+fn set_auto_backup(
+    state: State<'_, DatabaseConnection>,
+    enabled: bool,
+    interval_hours: u32,
+    dir: String,
+    keep: u32,
+) -> Result<(), String> {
+    let conn = state.lock()?;
+    backup::save_settings(&conn, &backup::AutoBackupSettings { enabled, interval_hours, dir: Some(dir), keep })
+}
+
+// None until the first automatic backup succeeds
+#[tauri::command]
+fn get_last_backup_time(state: State<'_, DatabaseConnection>) -> Result<Option<String>, String> {
+    let conn = state.lock()?;
+    backup::last_backup_time(&conn).map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// Database Diagnostics
 // ============================================================================
 
 #[tauri::command]
-fn get_weekly_summary(state: State<'_, DatabaseConnection>) -> Result<WeeklySummary, String> {
-    let conn = state.0.lock().map_err(|e| e.to_string())?;
-    
-    let now = Local::now();
-    let days_since_monday = now.weekday().num_days_from_monday() as i64;
-    let week_start = now - chrono::Duration::days(days_since_monday);
-    let week_start_str = week_start.format("%Y-%m-%d").to_string();
-    
-    // Get planned minutes from tasks due this week
-    let planned_minutes: i64 = conn
-        .query_row(
-            "SELECT COALESCE(SUM(COALESCE(estimated_minutes, 60)), 0) FROM tasks 
-             WHERE status != 'Done' AND due_date >= ?1 AND due_date <= date('now', '+7 days')",
-            rusqlite::params![&week_start_str],
-            |row| row.get(0),
-        )
-        .unwrap_or(0);
-    
-    // Get actual minutes tracked this week
-    let actual_minutes: i64 = conn
-        .query_row(
-            "SELECT COALESCE(SUM(duration), 0) / 60 FROM time_entries WHERE start_time >= ?1",
-            rusqlite::params![&week_start_str],
-            |row| row.get(0),
-        )
-        .unwrap_or(0);
-    
-    let completion_rate = if planned_minutes > 0 {
-        ((actual_minutes as f64) / (planned_minutes as f64) * 100.0).min(100.0)
-    } else {
-        100.0
+fn check_foreign_keys(state: State<'_, DatabaseConnection>) -> Result<bool, String> {
+    let conn = state.lock()?;
+    conn.query_row("PRAGMA foreign_keys", [], |row| row.get::<_, i64>(0))
+        .map(|enabled| enabled == 1)
+        .map_err(|e| e.to_string())
+}
+
+// Tables and indexes init_database is expected to have created
+const EXPECTED_TABLES: [&str; 12] = [
+    "tasks",
+    "task_history",
+    "task_notes",
+    "time_entries",
+    "expenses",
+    "category_rules",
+    "category_budgets",
+    "subjects",
+    "exams",
+    "study_streaks",
+    "app_settings",
+    "reminders",
+];
+const EXPECTED_INDEXES: [&str; 1] = ["idx_reminders_unique"];
+
+#[tauri::command]
+fn self_test(state: State<'_, DatabaseConnection>) -> Result<SelfTestReport, String> {
+    let conn = state.lock()?;
+    let mut checks = Vec::new();
+    let mut check = |name: &str, result: Result<(), String>| {
+        checks.push(SelfTestCheck {
+            name: name.to_string(),
+            passed: result.is_ok(),
+            detail: result.err(),
+        });
     };
-    
-    let days_into_week = days_since_monday + 1;
-    let expected_progress = (days_into_week as f64 / 7.0) * 100.0;
-    let on_track = completion_rate >= expected_progress * 0.8;
-    
-    let pace_warning = if planned_minutes > 0 && completion_rate < 50.0 && days_into_week >= 3 {
-        let remaining = planned_minutes - actual_minutes;
-        let days_left = 7 - days_into_week;
-        if days_left > 0 {
-            Some(format!("At this pace, you need {} min/day to catch up.", remaining / days_left))
+
+    check(
+        "database_reachable",
+        conn.query_row("SELECT 1", [], |row| row.get::<_, i64>(0))
+            .map(|_| ())
+            .map_err(|e| e.to_string()),
+    );
+
+    let schema_objects = |kind: &str| -> Result<Vec<String>, String> {
+        let mut stmt = conn
+            .prepare("SELECT name FROM sqlite_master WHERE type = ?1")
+            .map_err(|e| e.to_string())?;
+        let names = stmt
+            .query_map(rusqlite::params![kind], |row| row.get(0))
+            .map_err(|e| e.to_string())?
+            .collect::<SqliteResult<Vec<String>>>()
+            .map_err(|e| e.to_string());
+        names
+    };
+    let missing = |kind: &str, expected: &[&str]| -> Result<(), String> {
+        let existing = schema_objects(kind)?;
+        let missing: Vec<&str> = expected
+            .iter()
+            .copied()
+            .filter(|name| !existing.iter().any(|existing| existing == name))
+            .collect();
+        if missing.is_empty() {
+            Ok(())
         } else {
-            None
+            Err(format!("Missing: {}", missing.join(", ")))
         }
-    } else {
-        None
     };
-    
-    Ok(WeeklySummary {
-        planned_minutes,
-        actual_minutes,
-        completion_rate,
-        on_track,
-        pace_warning,
-    })
+    check("tables_present", missing("table", &EXPECTED_TABLES));
+    check("indexes_present", missing("index", &EXPECTED_INDEXES));
+
+    check(
+        "foreign_keys_enabled",
+        conn.query_row("PRAGMA foreign_keys", [], |row| row.get::<_, i64>(0))
+            .map_err(|e| e.to_string())
+            .and_then(|enabled| if enabled == 1 { Ok(()) } else { Err("PRAGMA foreign_keys is off".to_string()) }),
+    );
+
+    check(
+        "reminder_worker_alive",
+        if reminders::worker_is_alive() { Ok(()) } else { Err("No reminder check has run recently".to_string()) },
+    );
+
+    // Write and remove a probe file next to the database
+    let data_dir_writable = match conn.path().map(PathBuf::from).and_then(|path| path.parent().map(PathBuf::from)) {
+        Some(dir) => {
+            let probe = dir.join(".zentrack-write-test");
+            std::fs::write(&probe, b"ok")
+                .and_then(|_| std::fs::remove_file(&probe))
+                .map_err(|e| format!("Cannot write to '{}': {}", dir.display(), e))
+        }
+        None => Err("Database has no file path".to_string()),
+    };
+    check("data_directory_writable", data_dir_writable);
+
+    let passed = checks.iter().all(|check| check.passed);
+    Ok(SelfTestReport { passed, checks })
 }
 
-// ============================================================================
-// App Settings
-// ============================================================================
+// Rows the app's own parsing would reject or silently misread. Columns are read as raw values
+// so a wrongly typed cell is reported rather than failing the scan.
+fn find_data_issues(conn: &Connection) -> SqliteResult<Vec<DataIssue>> {
+    let mut issues = Vec::new();
+
+    let text = |value: &Value| match value {
+        Value::Text(text) => Some(text.clone()),
+        _ => None,
+    };
+
+    let rows = |sql: &str| -> SqliteResult<Vec<(i64, Value, Value)>> {
+        let mut stmt = conn.prepare(sql)?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?;
+        rows.collect()
+    };
+
+    for (id, due_date, tags) in rows("SELECT id, due_date, tags FROM tasks ORDER BY id")? {
+        let mut issue = |problem: String| issues.push(DataIssue { table: "tasks".to_string(), row_id: id, problem });
+
+        match due_date {
+            Value::Null => {}
+            Value::Text(ref due) if due.trim().is_empty() => {}
+            Value::Text(ref due) if reminders::normalize_datetime(due).is_some() => {}
+            other => issue(format!("Unreadable due date {:?}", text(&other).unwrap_or_default())),
+        }
+
+        let tags_ok = text(&tags).is_some_and(|tags| serde_json::from_str::<Vec<String>>(&tags).is_ok());
+        if !tags_ok {
+            issue("Tags are not a JSON array of strings".to_string());
+        }
+    }
+
+    for (id, remind_at, _) in rows("SELECT id, remind_at, NULL FROM reminders ORDER BY id")? {
+        if text(&remind_at).and_then(|remind_at| reminders::normalize_datetime(&remind_at)).is_none() {
+            issues.push(DataIssue {
+                table: "reminders".to_string(),
+                row_id: id,
+                problem: format!("Unreadable reminder time {:?}", text(&remind_at).unwrap_or_default()),
+            });
+        }
+    }
+
+    for (id, amount, _) in rows("SELECT id, amount, NULL FROM expenses ORDER BY id")? {
+        let problem = match amount {
+            Value::Integer(amount) if amount < 0 => Some(format!("Negative amount {}", amount)),
+            Value::Integer(_) => None,
+            Value::Real(amount) if !amount.is_finite() => Some(format!("Amount {} is not a finite number", amount)),
+            Value::Real(amount) if amount < 0.0 => Some(format!("Negative amount {}", amount)),
+            Value::Real(_) => None,
+            _ => Some("Amount is not a number".to_string()),
+        };
+        if let Some(problem) = problem {
+            issues.push(DataIssue { table: "expenses".to_string(), row_id: id, problem });
+        }
+    }
+
+    for (id, start_time, end_time) in rows("SELECT id, start_time, end_time FROM time_entries ORDER BY id")? {
+        let parse = |value: &Value| text(value).and_then(|time| DateTime::parse_from_rfc3339(&time).ok());
+        let problem = match (parse(&start_time), &end_time) {
+            (None, _) => Some("Unreadable start time".to_string()),
+            (Some(_), Value::Null) => None,
+            (Some(start), end) => match parse(end) {
+                None => Some("Unreadable end time".to_string()),
+                Some(end) if end < start => Some("Ends before it starts".to_string()),
+                Some(_) => None,
+            },
+        };
+        if let Some(problem) = problem {
+            issues.push(DataIssue { table: "time_entries".to_string(), row_id: id, problem });
+        }
+    }
+
+    Ok(issues)
+}
 
 #[tauri::command]
-fn get_app_setting(state: State<'_, DatabaseConnection>, key: String) -> Result<Option<String>, String> {
-    let conn = state.0.lock().map_err(|e| e.to_string())?;
-    
-    let result: Option<String> = conn
-        .query_row(
-            "SELECT value FROM app_settings WHERE key = ?1",
-            rusqlite::params![&key],
-            |row| row.get(0),
-        )
-        .optional()
-        .map_err(|e| e.to_string())?;
-    
-    Ok(result)
+fn validate_data(state: State<'_, DatabaseConnection>) -> Result<Vec<DataIssue>, String> {
+    let conn = state.lock()?;
+    find_data_issues(&conn).map_err(|e| e.to_string())
+}
+
+// Best-effort tag list from a tags cell that isn't a JSON string array: array elements are kept
+// as text, and anything else is read as a comma- or semicolon-separated list
+fn recover_tags(raw: Option<&str>) -> Vec<String> {
+    let raw = match raw {
+        Some(raw) => raw,
+        None => return Vec::new(),
+    };
+
+    let listed = match serde_json::from_str::<serde_json::Value>(raw) {
+        Ok(serde_json::Value::Array(items)) => items
+            .into_iter()
+            .filter_map(|item| match item {
+                serde_json::Value::String(tag) => Some(tag),
+                serde_json::Value::Number(number) => Some(number.to_string()),
+                serde_json::Value::Bool(flag) => Some(flag.to_string()),
+                _ => None,
+            })
+            .collect(),
+        Ok(serde_json::Value::String(text)) => text.split([',', ';']).map(str::to_string).collect(),
+        Ok(_) => Vec::new(),
+        Err(_) => raw.split([',', ';']).map(str::to_string).collect::<Vec<String>>(),
+    };
+
+    listed
+        .into_iter()
+        .map(|tag| tag.trim().to_string())
+        .filter(|tag| !tag.is_empty())
+        .collect()
 }
 
+// Rewrites every tags cell that load_tasks would otherwise read as empty; returns how many
+// rows changed. Recovered tags are normalized like new ones, but kept as-is if they break the
+// configured limits, so nothing more is lost.
 #[tauri::command]
-fn set_app_setting(state: State<'_, DatabaseConnection>, key: String, value: String) -> Result<(), String> {
-    let conn = state.0.lock().map_err(|e| e.to_string())?;
-    
-    conn.execute(
-        "INSERT OR REPLACE INTO app_settings (key, value, updated_at) VALUES (?1, ?2, datetime('now'))",
-        rusqlite::params![&key, &value],
-    )
-    .map_err(|e| e.to_string())?;
-    
-    Ok(())
+fn repair_tags(state: State<'_, DatabaseConnection>) -> Result<u64, String> {
+    let mut conn = state.lock()?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+
+    let broken: Vec<(i64, Option<String>)> = {
+        let mut stmt = tx.prepare("SELECT id, tags FROM tasks").map_err(|e| e.to_string())?;
+        let rows = stmt
+            .query_map([], |row| {
+                let tags = match row.get::<_, Value>(1)? {
+                    Value::Text(tags) => Some(tags),
+                    _ => None,
+                };
+                Ok((row.get::<_, i64>(0)?, tags))
+            })
+            .map_err(|e| e.to_string())?
+            .collect::<SqliteResult<Vec<_>>>()
+            .map_err(|e| e.to_string())?;
+        rows.into_iter()
+            .filter(|(_, tags)| match tags {
+                Some(tags) => serde_json::from_str::<Vec<String>>(tags).is_err(),
+                None => true,
+            })
+            .collect()
+    };
+
+    for (id, raw) in &broken {
+        let recovered = recover_tags(raw.as_deref());
+        let tags = validate_tags(&tx, &recovered).unwrap_or(recovered);
+        let tags_json = serde_json::to_string(&tags).map_err(|e| e.to_string())?;
+        tx.execute("UPDATE tasks SET tags = ?1 WHERE id = ?2", rusqlite::params![&tags_json, id])
+            .map_err(|e| e.to_string())?;
+    }
+
+    tx.commit().map_err(|e| e.to_string())?;
+
+    Ok(broken.len() as u64)
+}
+
+#[tauri::command]
+fn get_database_stats(state: State<'_, DatabaseConnection>) -> Result<DbStats, String> {
+    let conn = state.lock()?;
+
+    let count = |table: &str| -> Result<i64, String> {
+        conn.query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| row.get(0))
+            .map_err(|e| e.to_string())
+    };
+
+    let file_size_bytes = conn
+        .path()
+        .and_then(|path| std::fs::metadata(path).ok())
+        .map(|meta| meta.len())
+        .unwrap_or(0);
+
+    let schema_version: i64 = conn
+        .query_row("PRAGMA user_version", [], |row| row.get(0))
+        .map_err(|e| e.to_string())?;
+
+    Ok(DbStats {
+        task_count: count("tasks")?,
+        time_entry_count: count("time_entries")?,
+        expense_count: count("expenses")?,
+        reminder_count: count("reminders")?,
+        file_size_bytes,
+        schema_version,
+    })
 }
 
 fn main() {
-    let conn = init_database().expect("Failed to initialize database");
+    let db_path = get_db_path().expect("Failed to resolve data directory");
+    // An encrypted database starts locked on a placeholder; the frontend asks for the passphrase
+    let conn = match init_database(db_path.clone(), None) {
+        Ok(conn) => conn,
+        Err(err) if encryption::is_not_a_database(&err) => {
+            encryption::set_locked(Some(db_path));
+            Connection::open_in_memory().expect("Failed to open placeholder database")
+        }
+        Err(err) => panic!("Failed to initialize database: {}", err),
+    };
     let db_state = DatabaseConnection(Arc::new(Mutex::new(conn)));
     
     tauri::Builder::default()
@@ -1039,25 +4643,115 @@ fn main() {
         .setup(|app| {
             let app_handle = app.handle();
             let db = app.state::<DatabaseConnection>().inner().clone();
-            reminders::start_reminder_worker(app_handle.clone(), db);
+            reminders::start_reminder_worker(app_handle.clone(), db.clone());
+            backup::start_backup_worker(db);
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             get_tasks,
+            get_tasks_filtered,
+            get_tasks_sorted,
+            query_tasks,
+            get_tasks_this_week,
+            get_tasks_due_between,
+            get_stale_in_progress,
+            get_tasks_without_due_date,
+            export_tasks,
+            export_all,
+            get_auto_backup,
+            set_auto_backup,
+            get_last_backup_time,
+            create_profile,
+            switch_profile,
+            list_profiles,
+            get_active_profile,
+            import_all,
             add_task,
+            add_task_returning_id,
+            quick_add_task,
             update_task,
+            patch_task,
+            set_task_status,
+            bulk_set_due_date,
+            reschedule_overdue_to_today,
+            toggle_pin,
+            get_task_history,
+            add_task_note,
+            get_task_notes,
+            get_task_detail,
+            delete_task_note,
             delete_task,
+            find_duplicate_tasks,
+            get_related_tags,
+            get_overdue_by_priority,
             create_reminder,
             get_reminders_for_task,
+            get_reminders_for_task_detailed,
+            get_tasks_with_upcoming_reminders,
             delete_reminder,
+            reset_reminder,
+            acknowledge_reminder,
+            get_pending_reminders,
+            remind_all_overdue,
+            cleanup_old_reminders,
+            export_reminders,
+            import_reminders,
+            find_orphan_reminders,
+            delete_orphan_reminders,
+            get_reminder_counts,
+            get_worker_status,
+            get_next_reminder,
+            copy_reminders,
+            shift_task_reminders,
             get_time_entries,
             add_time_entry,
+            add_time_entry_returning_id,
             update_time_entry,
             delete_time_entry,
+            archive_time_entry,
+            get_archived_time_entries,
+            get_time_per_task,
+            get_estimate_accuracy,
+            get_daily_time_log,
+            get_daily_goal_progress,
+            round_duration,
+            get_time_rounding,
+            set_time_rounding,
+            split_time_entry,
+            close_open_timers,
+            recategorize_time_entries,
+            rename_time_category,
+            get_time_categories,
+            reschedule_time_entry,
             get_expenses,
             add_expense,
+            import_expenses_csv,
+            get_top_expenses,
+            get_category_detail,
+            get_category_stats,
+            rename_expense_category,
+            get_expense_categories,
+            add_expense_returning_id,
             update_expense,
             delete_expense,
+            get_category_rules,
+            add_category_rule,
+            get_monthly_expense_trend,
+            get_category_mom_delta,
+            get_savings_rate,
+            get_average_daily_spend,
+            get_projected_month_spend,
+            get_cashflow_series,
+            get_balance_timeline,
+            get_completions_per_day,
+            get_completions_by_weekday,
+            get_average_cycle_time,
+            estimate_completion,
+            set_budget_anchor_day,
+            get_category_budgets,
+            set_category_budget,
+            get_current_period_spending,
+            export_monthly_report_pdf,
             // New student-focused commands
             get_subjects,
             add_subject,
@@ -1070,8 +4764,261 @@ fn main() {
             get_next_focus_item,
             get_weekly_summary,
             get_app_setting,
-            set_app_setting
+            set_app_setting,
+            get_theme,
+            set_theme,
+            get_daily_digest_time,
+            is_database_locked,
+            unlock_database,
+            set_encryption,
+            set_daily_digest_time,
+            get_daily_time_goal,
+            set_daily_time_goal,
+            get_last_view,
+            set_last_view,
+            get_week_start_day,
+            set_week_start_day,
+            get_database_stats,
+            check_foreign_keys,
+            validate_data,
+            repair_tags,
+            self_test
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Each test names its own shared in-memory database, so init_database can open it a second
+    // time while the first connection keeps it alive
+    fn shared_memory_path(name: &str) -> PathBuf {
+        PathBuf::from(format!("file:{}?mode=memory&cache=shared", name))
+    }
+
+    fn due_date_of(conn: &Connection, id: i64) -> Option<String> {
+        conn.query_row("SELECT due_date FROM tasks WHERE id = ?1", rusqlite::params![id], |row| row.get(0))
+            .unwrap()
+    }
+
+    #[test]
+    fn empty_due_date_migration_runs_once() {
+        let path = shared_memory_path("empty_due_date_migration");
+        let conn = init_database(path.clone(), None).unwrap();
+        let stored: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0)).unwrap();
+        assert_eq!(stored, SCHEMA_VERSION);
+
+        // A legacy "" written before the migration: converted when the file is older than version 8
+        conn.execute(
+            "INSERT INTO tasks (title, description, due_date, tags, priority, status) VALUES ('Old', '', '', '[]', 'Low', 'Pending')",
+            [],
+        )
+        .unwrap();
+        let id = conn.last_insert_rowid();
+        conn.pragma_update(None, "user_version", 7).unwrap();
+
+        let _reopened = init_database(path.clone(), None).unwrap();
+        assert_eq!(due_date_of(&conn, id), None);
+
+        // Already at the current version, so a second run leaves the column alone
+        conn.execute("UPDATE tasks SET due_date = '' WHERE id = ?1", rusqlite::params![id]).unwrap();
+        let _reopened_again = init_database(path, None).unwrap();
+        assert_eq!(due_date_of(&conn, id).as_deref(), Some(""));
+    }
+
+    #[test]
+    fn empty_due_date_round_trips_as_none() {
+        let conn = init_database(shared_memory_path("empty_due_date_round_trip"), None).unwrap();
+        let task = Task {
+            id: 0,
+            title: "No date".to_string(),
+            description: String::new(),
+            due_date: Some("  ".to_string()),
+            tags: Vec::new(),
+            priority: "Medium".to_string(),
+            status: "Pending".to_string(),
+            subject_id: None,
+            estimated_minutes: None,
+            actual_minutes: None,
+            is_pinned: false,
+            created_at: None,
+            status_changed_at: None,
+            completed_at: None,
+            color: None,
+        };
+
+        let id = insert_task(&conn, &task).unwrap();
+        assert_eq!(due_date_of(&conn, id), None);
+        assert_eq!(load_task(&conn, id).unwrap().unwrap().due_date, None);
+    }
+
+    #[test]
+    fn connections_get_the_required_pragmas() {
+        // WAL needs a real file; an in-memory database always reports "memory"
+        let path = std::env::temp_dir().join(format!("zentrack-pragmas-{}.db", std::process::id()));
+        let conn = open_connection(path.clone(), None).unwrap();
+
+        let foreign_keys: i64 = conn.query_row("PRAGMA foreign_keys", [], |row| row.get(0)).unwrap();
+        let journal_mode: String = conn.query_row("PRAGMA journal_mode", [], |row| row.get(0)).unwrap();
+        let busy_timeout: u64 = conn.query_row("PRAGMA busy_timeout", [], |row| row.get(0)).unwrap();
+        drop(conn);
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{}", path.display(), suffix));
+        }
+
+        assert_eq!(foreign_keys, 1);
+        assert_eq!(journal_mode, "wal");
+        assert_eq!(busy_timeout, BUSY_TIMEOUT_MS);
+    }
+
+    #[test]
+    fn machine_local_settings_stay_out_of_backups() {
+        let conn = init_database(shared_memory_path("machine_local_settings"), None).unwrap();
+        write_setting(&conn, backup::DIR_KEY, "/home/me/backups").unwrap();
+        write_setting(&conn, reminders::DAILY_DIGEST_LAST_SENT_KEY, "2026-10-13").unwrap();
+        write_setting(&conn, THEME_KEY, "light").unwrap();
+
+        let exported: Vec<String> = load_all_settings(&conn).unwrap().into_iter().map(|setting| setting.key).collect();
+        assert!(exported.contains(&THEME_KEY.to_string()));
+        assert!(!exported.iter().any(|key| is_machine_local_setting(key)));
+
+        // A backup from another machine, or an older one exported before the deny-list existed
+        let setting = |key: &str, value: &str| AppSetting { key: key.to_string(), value: value.to_string() };
+        let data = export::ExportData {
+            app_settings: vec![
+                setting(backup::DIR_KEY, "D:\\elsewhere"),
+                setting(backup::ENABLED_KEY, "true"),
+                setting(reminders::DAILY_DIGEST_LAST_SENT_KEY, "2026-10-14"),
+                setting(THEME_KEY, "dark"),
+            ],
+            ..Default::default()
+        };
+        restore_export_data(&conn, &data, |_, _| {}).unwrap();
+
+        assert_eq!(read_setting(&conn, backup::DIR_KEY).unwrap().as_deref(), Some("/home/me/backups"));
+        assert_eq!(read_setting(&conn, backup::ENABLED_KEY).unwrap(), None);
+        assert_eq!(read_setting(&conn, reminders::DAILY_DIGEST_LAST_SENT_KEY).unwrap().as_deref(), Some("2026-10-13"));
+        assert_eq!(read_setting(&conn, THEME_KEY).unwrap().as_deref(), Some("dark"));
+    }
+
+    #[test]
+    fn rounding_an_empty_entry_bills_one_interval() {
+        assert_eq!(round_up_to_interval(0, 15), 15 * 60);
+        assert_eq!(
+            rounded_span("2026-10-14T09:00:00.000Z", 0, 15),
+            Some((15 * 60, "2026-10-14T09:15:00.000Z".to_string()))
+        );
+    }
+
+    #[test]
+    fn rounding_keeps_exact_multiples() {
+        assert_eq!(round_up_to_interval(30 * 60, 15), 30 * 60);
+        assert_eq!(round_up_to_interval(30 * 60 + 1, 15), 45 * 60);
+        assert_eq!(
+            rounded_span("2026-10-14T09:00:00.000Z", 30 * 60, 15),
+            Some((30 * 60, "2026-10-14T09:30:00.000Z".to_string()))
+        );
+    }
+
+    #[test]
+    fn auto_rounding_applies_only_when_set_and_finished() {
+        let conn = init_database(shared_memory_path("auto_rounding"), None).unwrap();
+        let entry = TimeEntry {
+            id: 0,
+            task: "Invoice".to_string(),
+            start_time: "2026-10-14T09:00:00.000Z".to_string(),
+            end_time: Some("2026-10-14T09:07:00.000Z".to_string()),
+            duration: 7 * 60,
+            category: "Work".to_string(),
+            subject_id: None,
+            task_id: None,
+            archived: false,
+            description: String::new(),
+        };
+
+        let mut unrounded = entry.clone();
+        auto_round_entry(&conn, &mut unrounded).unwrap();
+        assert_eq!(unrounded.duration, 7 * 60);
+
+        write_setting(&conn, TIME_ROUNDING_KEY, "15").unwrap();
+        let mut running = TimeEntry { end_time: None, ..entry.clone() };
+        auto_round_entry(&conn, &mut running).unwrap();
+        assert_eq!(running.duration, 7 * 60);
+
+        let mut finished = entry;
+        auto_round_entry(&conn, &mut finished).unwrap();
+        assert_eq!(finished.duration, 15 * 60);
+        assert_eq!(finished.end_time.as_deref(), Some("2026-10-14T09:15:00.000Z"));
+    }
+
+    #[test]
+    fn due_filter_normalizes_mixed_due_date_formats() {
+        let conn = init_database(shared_memory_path("mixed_due_dates"), None).unwrap();
+        for (title, due_date, status) in [
+            ("local", "2026-10-14T10:00", "Pending"),
+            ("rfc3339", "2026-10-14T08:00:00+00:00", "Pending"),
+            ("spaced", "2026-10-14 09:00:00", "In Progress"),
+            ("unreadable", "next week", "Pending"),
+            ("done", "2026-10-14T11:00", "Done"),
+            ("outside", "2026-11-20T10:00", "Pending"),
+        ] {
+            conn.execute(
+                "INSERT INTO tasks (title, description, due_date, tags, priority, status) VALUES (?1, '', ?2, '[]', 'Low', ?3)",
+                rusqlite::params![title, due_date, status],
+            )
+            .unwrap();
+        }
+
+        let start = reminders::normalize_datetime("2026-10-12T00:00").unwrap();
+        let end = reminders::normalize_datetime("2026-10-19T00:00").unwrap();
+        let tasks = load_tasks_due_where(&conn, false, |due| due >= start && due < end).unwrap();
+
+        let mut titles: Vec<&str> = tasks.iter().map(|task| task.title.as_str()).collect();
+        titles.sort_unstable();
+        assert_eq!(titles, ["local", "rfc3339", "spaced"]);
+    }
+
+    #[test]
+    fn monthly_report_hours_skip_archived_entries_unless_asked() {
+        let conn = init_database(shared_memory_path("monthly_report_archived"), None).unwrap();
+        for (task, duration, archived) in [("live", 3600, false), ("old", 7200, true)] {
+            conn.execute(
+                "INSERT INTO time_entries (task, start_time, end_time, duration, category, archived) VALUES (?1, '2026-10-05T09:00:00.000Z', '2026-10-05T12:00:00.000Z', ?2, 'Study', ?3)",
+                rusqlite::params![task, duration, archived],
+            )
+            .unwrap();
+        }
+
+        let visible = load_monthly_report(&conn, 2026, 10, false).unwrap();
+        assert_eq!(visible.hours_by_category, [("Study".to_string(), 1.0)]);
+
+        let everything = load_monthly_report(&conn, 2026, 10, true).unwrap();
+        assert_eq!(everything.hours_by_category, [("Study".to_string(), 3.0)]);
+    }
+
+    #[test]
+    fn patch_rejects_unknown_status_and_priority() {
+        let mut conn = init_database(shared_memory_path("patch_validation"), None).unwrap();
+        conn.execute(
+            "INSERT INTO tasks (title, description, tags, priority, status) VALUES ('essay', '', '[]', 'Low', 'Pending')",
+            [],
+        )
+        .unwrap();
+        let id = conn.last_insert_rowid() as u64;
+        let fields = |conn: &Connection| -> (String, String) {
+            conn.query_row("SELECT status, priority FROM tasks WHERE id = ?1", rusqlite::params![id as i64], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })
+            .unwrap()
+        };
+
+        assert!(apply_task_patch(&mut conn, id, Some("Archived".to_string()), Some("High".to_string()), None, None).is_err());
+        assert!(apply_task_patch(&mut conn, id, Some("Done".to_string()), Some("Urgent".to_string()), None, None).is_err());
+        assert_eq!(fields(&conn), ("Pending".to_string(), "Low".to_string()));
+
+        apply_task_patch(&mut conn, id, Some("In Progress".to_string()), Some("High".to_string()), None, None).unwrap();
+        assert_eq!(fields(&conn), ("In Progress".to_string(), "High".to_string()));
+    }
+}
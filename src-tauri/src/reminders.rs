@@ -1,15 +1,49 @@
-use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
-use rusqlite::{params, Connection};
+use chrono::{DateTime, Local, NaiveDateTime, NaiveTime, TimeZone, Timelike, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
 use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Mutex;
 use std::time::Duration;
 use std::thread;
-use tauri::AppHandle;
+use tauri::{AppHandle, Emitter, Manager};
 use tauri_plugin_notification::NotificationExt;
 
 use crate::DatabaseConnection;
 
 const CHECK_INTERVAL_SECS: u64 = 30;
 
+// Unix time of the worker's last pass, for the self-test; 0 until the worker starts
+static LAST_WORKER_PASS: AtomicI64 = AtomicI64::new(0);
+
+// Reminders shown in the last few minutes, as (id, remind_at, unix time shown, channel). If
+// marking one triggered fails after it was delivered, this stops the next passes from repeating
+// it. remind_at is part of the key so a snoozed or rescheduled reminder still fires.
+static RECENTLY_FIRED: Mutex<Vec<(i64, String, i64, DeliveryChannel)>> = Mutex::new(Vec::new());
+const DUPLICATE_WINDOW_SECS: i64 = 10 * 60;
+
+// Cap on a task's untriggered reminders, so a runaway number can't bloat the worker's join
+const MAX_PENDING_PER_TASK_KEY: &str = "max_pending_reminders_per_task";
+const DEFAULT_MAX_PENDING_PER_TASK: i64 = 10;
+
+// More reminders than this in one pass are grouped into a single summary notification
+const GROUP_THRESHOLD_KEY: &str = "reminder_group_threshold";
+const DEFAULT_GROUP_THRESHOLD: usize = 5;
+const SUMMARY_TITLE_COUNT: usize = 3;
+
+// Upper bound when searching past a DST gap for the next valid local time
+const MAX_DST_GAP_MINUTES: i64 = 24 * 60;
+
+// Sound for High priority reminders; desktop platforms name their default sound differently
+#[cfg(target_os = "linux")]
+const URGENT_SOUND: &str = "message-new-instant";
+#[cfg(not(target_os = "linux"))]
+const URGENT_SOUND: &str = "Default";
+
+// Emitted for the frontend to show an in-app toast when the OS notification can't be shown
+pub const REMINDER_TOAST_EVENT: &str = "reminder-toast";
+
+const REMINDER_TASK_JOIN: &str = "FROM reminders r INNER JOIN tasks t ON t.id = r.task_id";
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Reminder {
     pub id: i64,
@@ -17,6 +51,78 @@ pub struct Reminder {
     pub remind_at: String,
     pub triggered: bool,
     pub created_at: String,
+    #[serde(default)]
+    pub acknowledged: bool, // the user has dealt with it; independent of `triggered`
+    #[serde(default)]
+    pub delivered_via: Option<String>, // "os" or "in_app" once fired, for diagnostics
+    #[serde(default)]
+    pub delivered_at: Option<String>, // None for triggered reminders that never reached the user
+}
+
+// One entry of a standalone reminder export; the title lets it find its task in another database
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ScheduledReminder {
+    #[serde(default)]
+    pub task_id: Option<i64>,
+    pub task_title: String,
+    pub remind_at: String,
+    #[serde(default)]
+    pub triggered: bool,
+    #[serde(default)]
+    pub acknowledged: bool,
+    #[serde(default)]
+    pub delivered_via: Option<String>,
+    #[serde(default)]
+    pub delivered_at: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ReminderImportReport {
+    pub imported: u64,
+    pub skipped: Vec<String>, // one line per reminder left out, with the reason
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ReminderToast {
+    pub reminder_ids: Vec<i64>,
+    pub title: String,
+    pub body: String,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum DeliveryChannel {
+    Os,
+    InApp,
+}
+
+impl DeliveryChannel {
+    fn as_str(self) -> &'static str {
+        match self {
+            DeliveryChannel::Os => "os",
+            DeliveryChannel::InApp => "in_app",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ReminderWithTask {
+    pub id: i64,
+    pub task_id: i64,
+    pub remind_at: String,
+    pub triggered: bool,
+    pub created_at: String,
+    pub task_title: String,
+    pub task_status: String,
+    pub task_priority: String,
+    pub task_due_date: Option<String>,
+    pub acknowledged: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct WorkerStatus {
+    pub alive: bool,
+    pub last_run: Option<String>, // None until the first pass
+    pub check_interval_secs: u64,
 }
 
 #[derive(Debug)]
@@ -31,6 +137,53 @@ struct PendingReminderRow {
     tags: String,
 }
 
+const REMINDER_COLUMNS: &str = "id, task_id, remind_at, triggered, created_at, acknowledged, delivered_via, delivered_at";
+
+fn reminder_from_row(row: &rusqlite::Row) -> rusqlite::Result<Reminder> {
+    Ok(Reminder {
+        id: row.get(0)?,
+        task_id: row.get(1)?,
+        remind_at: row.get(2)?,
+        triggered: row.get::<_, i64>(3)? != 0,
+        created_at: row.get(4)?,
+        acknowledged: row.get::<_, i64>(5)? != 0,
+        delivered_via: row.get(6)?,
+        delivered_at: row.get(7)?,
+    })
+}
+
+const REMINDER_WITH_TASK_COLUMNS: &str =
+    "r.id, r.task_id, r.remind_at, r.triggered, r.created_at, t.title, t.status, t.priority, t.due_date, r.acknowledged";
+
+fn reminder_with_task_from_row(row: &rusqlite::Row) -> rusqlite::Result<ReminderWithTask> {
+    Ok(ReminderWithTask {
+        id: row.get(0)?,
+        task_id: row.get(1)?,
+        remind_at: row.get(2)?,
+        triggered: row.get::<_, i64>(3)? != 0,
+        created_at: row.get(4)?,
+        task_title: row.get(5)?,
+        task_status: row.get(6)?,
+        task_priority: row.get(7)?,
+        task_due_date: row.get(8)?,
+        acknowledged: row.get::<_, i64>(9)? != 0,
+    })
+}
+
+fn load_reminder(conn: &Connection, reminder_id: i64) -> rusqlite::Result<Reminder> {
+    conn.query_row(
+        &format!("SELECT {} FROM reminders WHERE id = ?1", REMINDER_COLUMNS),
+        params![reminder_id],
+        reminder_from_row,
+    )
+}
+
+fn task_exists(conn: &Connection, task_id: i64) -> rusqlite::Result<bool> {
+    conn.query_row("SELECT 1 FROM tasks WHERE id = ?1", params![task_id], |_| Ok(()))
+        .optional()
+        .map(|row| row.is_some())
+}
+
 pub fn init_reminders_table(conn: &Connection) -> rusqlite::Result<()> {
     conn.execute(
         "CREATE TABLE IF NOT EXISTS reminders (
@@ -49,14 +202,55 @@ pub fn init_reminders_table(conn: &Connection) -> rusqlite::Result<()> {
         [],
     )?;
 
+    // Whether the user dealt with a reminder, separate from the worker's `triggered`
+    let _ = conn.execute("ALTER TABLE reminders ADD COLUMN acknowledged INTEGER NOT NULL DEFAULT 0", []);
+    let _ = conn.execute("ALTER TABLE reminders ADD COLUMN delivered_via TEXT", []);
+    // Reminders triggered before delivery was recorded are assumed delivered when due; only
+    // done once, as the column is added, so later undelivered ones stay visible
+    if conn.execute("ALTER TABLE reminders ADD COLUMN delivered_at DATETIME", []).is_ok() {
+        conn.execute("UPDATE reminders SET delivered_at = remind_at WHERE triggered = 1", [])?;
+    }
+
+    Ok(())
+}
+
+// Fired reminders don't count; they no longer cost the worker anything
+fn check_pending_cap(conn: &Connection, task_id: i64) -> Result<(), String> {
+    let max_pending = crate::read_setting(conn, MAX_PENDING_PER_TASK_KEY)
+        .map_err(|e| e.to_string())?
+        .and_then(|value| value.parse::<i64>().ok())
+        .filter(|max| *max > 0)
+        .unwrap_or(DEFAULT_MAX_PENDING_PER_TASK);
+    let pending: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM reminders WHERE task_id = ?1 AND triggered = 0",
+            params![task_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+    if pending >= max_pending {
+        return Err(format!("A task can have at most {} pending reminders", max_pending));
+    }
     Ok(())
 }
 
-pub fn create_reminder(conn: &Connection, task_id: i64, remind_at: String) -> Result<(), String> {
-    let normalized = normalize_datetime(&remind_at)
-        .map(|dt| dt.to_rfc3339())
+pub fn create_reminder(conn: &Connection, task_id: i64, remind_at: String, force: bool) -> Result<(), String> {
+    let remind_at_dt = normalize_datetime(&remind_at)
         .ok_or_else(|| "Invalid reminder time".to_string())?;
 
+    // Past reminders would fire instantly; only allow them when explicitly forced (e.g. imports)
+    if !force && remind_at_dt < Utc::now() {
+        return Err("Reminder time is in the past".to_string());
+    }
+
+    if !task_exists(conn, task_id).map_err(|e| e.to_string())? {
+        return Err("Task not found".to_string());
+    }
+
+    check_pending_cap(conn, task_id)?;
+
+    let normalized = remind_at_dt.to_rfc3339();
+
     conn
         .execute(
             "INSERT INTO reminders (task_id, remind_at) VALUES (?1, ?2)",
@@ -70,23 +264,17 @@ pub fn create_reminder(conn: &Connection, task_id: i64, remind_at: String) -> Re
     Ok(())
 }
 
-pub fn get_reminders_for_task(conn: &Connection, task_id: i64) -> Result<Vec<Reminder>, String> {
+pub fn get_reminders_for_task(conn: &Connection, task_id: i64, descending: bool) -> Result<Vec<Reminder>, String> {
     let mut stmt = conn
-        .prepare(
-            "SELECT id, task_id, remind_at, triggered, created_at FROM reminders WHERE task_id = ?1 ORDER BY remind_at",
-        )
+        .prepare(&format!(
+            "SELECT {} FROM reminders WHERE task_id = ?1 ORDER BY remind_at {}",
+            REMINDER_COLUMNS,
+            if descending { "DESC" } else { "ASC" }
+        ))
         .map_err(|e| e.to_string())?;
 
     let reminders = stmt
-        .query_map(params![task_id], |row| {
-            Ok(Reminder {
-                id: row.get(0)?,
-                task_id: row.get(1)?,
-                remind_at: row.get(2)?,
-                triggered: row.get::<_, i64>(3)? != 0,
-                created_at: row.get(4)?,
-            })
-        })
+        .query_map(params![task_id], reminder_from_row)
         .map_err(|e| e.to_string())?;
 
     let mut collection = Vec::new();
@@ -97,6 +285,80 @@ pub fn get_reminders_for_task(conn: &Connection, task_id: i64) -> Result<Vec<Rem
     Ok(collection)
 }
 
+pub fn load_all_reminders(conn: &Connection) -> rusqlite::Result<Vec<Reminder>> {
+    let mut stmt = conn.prepare(&format!("SELECT {} FROM reminders ORDER BY id", REMINDER_COLUMNS))?;
+    let reminders = stmt.query_map([], reminder_from_row)?;
+    reminders.collect()
+}
+
+pub fn get_reminders_for_task_detailed(conn: &Connection, task_id: i64) -> Result<Vec<ReminderWithTask>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} {} WHERE r.task_id = ?1 ORDER BY r.remind_at",
+            REMINDER_WITH_TASK_COLUMNS, REMINDER_TASK_JOIN
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let reminders = stmt
+        .query_map(params![task_id], reminder_with_task_from_row)
+        .map_err(|e| e.to_string())?;
+
+    reminders
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())
+}
+
+pub fn get_upcoming_reminders(conn: &Connection, hours: i64) -> Result<Vec<ReminderWithTask>, String> {
+    if hours < 0 {
+        return Err("Hours must not be negative".to_string());
+    }
+
+    let now = Utc::now();
+    let horizon = chrono::Duration::try_hours(hours)
+        .and_then(|window| now.checked_add_signed(window))
+        .ok_or_else(|| format!("A window of {} hours is out of range", hours))?;
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} {} WHERE r.triggered = 0 AND t.status != 'Done'",
+            REMINDER_WITH_TASK_COLUMNS, REMINDER_TASK_JOIN
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let rows = stmt
+        .query_map([], reminder_with_task_from_row)
+        .map_err(|e| e.to_string())?;
+
+    // Compare parsed instants rather than strings, as the worker does
+    let mut upcoming = Vec::new();
+    for row in rows {
+        let reminder = row.map_err(|e| e.to_string())?;
+        if let Some(remind_at_dt) = normalize_datetime(&reminder.remind_at) {
+            if remind_at_dt >= now && remind_at_dt <= horizon {
+                upcoming.push((remind_at_dt, reminder));
+            }
+        }
+    }
+    upcoming.sort_by_key(|(remind_at_dt, _)| *remind_at_dt);
+
+    Ok(upcoming.into_iter().map(|(_, reminder)| reminder).collect())
+}
+
+// (untriggered, triggered)
+pub fn get_reminder_counts(conn: &Connection) -> Result<(u64, u64), String> {
+    let count = |triggered: bool| -> Result<u64, String> {
+        conn.query_row(
+            "SELECT COUNT(*) FROM reminders WHERE triggered = ?1",
+            params![triggered],
+            |row| row.get::<_, i64>(0),
+        )
+        .map(|count| count as u64)
+        .map_err(|e| e.to_string())
+    };
+
+    Ok((count(false)?, count(true)?))
+}
+
 pub fn delete_reminder(conn: &Connection, reminder_id: i64) -> Result<(), String> {
     conn
         .execute("DELETE FROM reminders WHERE id = ?1", params![reminder_id])
@@ -104,6 +366,337 @@ pub fn delete_reminder(conn: &Connection, reminder_id: i64) -> Result<(), String
     Ok(())
 }
 
+pub fn reset_reminder(conn: &Connection, reminder_id: i64) -> Result<(), String> {
+    let updated = conn
+        .execute("UPDATE reminders SET triggered = 0, acknowledged = 0, delivered_via = NULL, delivered_at = NULL WHERE id = ?1", params![reminder_id])
+        .map_err(|e| e.to_string())?;
+
+    if updated == 0 {
+        return Err("Reminder not found".to_string());
+    }
+
+    Ok(())
+}
+
+pub fn acknowledge_reminder(conn: &Connection, reminder_id: i64) -> Result<(), String> {
+    let updated = conn
+        .execute("UPDATE reminders SET acknowledged = 1 WHERE id = ?1", params![reminder_id])
+        .map_err(|e| e.to_string())?;
+
+    if updated == 0 {
+        return Err("Reminder not found".to_string());
+    }
+
+    Ok(())
+}
+
+// Reminders still to fire on open tasks, soonest first. With `include_unacknowledged`, fired
+// reminders the user hasn't acknowledged yet are included too, for an inbox view.
+pub fn get_pending_reminders(conn: &Connection, include_unacknowledged: bool) -> Result<Vec<ReminderWithTask>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} {} WHERE (r.triggered = 0 OR (?1 AND r.acknowledged = 0)) AND t.status != 'Done'
+             ORDER BY r.remind_at",
+            REMINDER_WITH_TASK_COLUMNS, REMINDER_TASK_JOIN
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let reminders = stmt
+        .query_map(params![include_unacknowledged], reminder_with_task_from_row)
+        .map_err(|e| e.to_string())?;
+
+    reminders
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())
+}
+
+// The untriggered reminder on an open task that fires first, comparing parsed times since
+// older rows may store remind_at in other formats. Rows that don't parse are skipped here;
+// check_and_fire retires them.
+pub fn next_reminder(conn: &Connection) -> Result<Option<ReminderWithTask>, String> {
+    Ok(get_pending_reminders(conn, false)?
+        .into_iter()
+        .filter_map(|reminder| normalize_datetime(&reminder.remind_at).map(|at| (at, reminder)))
+        .min_by_key(|(at, _)| *at)
+        .map(|(_, reminder)| reminder))
+}
+
+pub fn load_reminder_schedule(conn: &Connection) -> Result<Vec<ScheduledReminder>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT r.task_id, t.title, r.remind_at, r.triggered, r.acknowledged, r.delivered_via, r.delivered_at {} ORDER BY r.remind_at, r.id",
+            REMINDER_TASK_JOIN
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let reminders = stmt
+        .query_map([], |row| {
+            Ok(ScheduledReminder {
+                task_id: row.get(0)?,
+                task_title: row.get(1)?,
+                remind_at: row.get(2)?,
+                triggered: row.get::<_, i64>(3)? != 0,
+                acknowledged: row.get::<_, i64>(4)? != 0,
+                delivered_via: row.get(5)?,
+                delivered_at: row.get(6)?,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(reminders)
+}
+
+// An entry's task_id is used only while that task still has the same title, since ids differ
+// between databases; otherwise the first task with that exact title is used, open ones first.
+// Reminders already present (the unique index on task and time) are skipped, not duplicated,
+// and pending ones are held to the same per-task cap as create_reminder.
+pub fn import_reminder_schedule(conn: &Connection, entries: &[ScheduledReminder]) -> Result<ReminderImportReport, String> {
+    let mut report = ReminderImportReport { imported: 0, skipped: Vec::new() };
+
+    for entry in entries {
+        let describe = || format!("'{}' at {}", entry.task_title, entry.remind_at);
+
+        let remind_at = match normalize_datetime(&entry.remind_at) {
+            Some(remind_at) => remind_at.to_rfc3339(),
+            None => {
+                report.skipped.push(format!("{}: unreadable reminder time", describe()));
+                continue;
+            }
+        };
+
+        let by_id = match entry.task_id {
+            Some(task_id) => conn
+                .query_row(
+                    "SELECT id FROM tasks WHERE id = ?1 AND title = ?2",
+                    params![task_id, &entry.task_title],
+                    |row| row.get::<_, i64>(0),
+                )
+                .optional()
+                .map_err(|e| e.to_string())?,
+            None => None,
+        };
+        let task_id = match by_id {
+            Some(task_id) => Some(task_id),
+            None => conn
+                .query_row(
+                    "SELECT id FROM tasks WHERE title = ?1 ORDER BY status = 'Done', id LIMIT 1",
+                    params![&entry.task_title],
+                    |row| row.get::<_, i64>(0),
+                )
+                .optional()
+                .map_err(|e| e.to_string())?,
+        };
+        let task_id = match task_id {
+            Some(task_id) => task_id,
+            None => {
+                report.skipped.push(format!("{}: no matching task", describe()));
+                continue;
+            }
+        };
+
+        if !entry.triggered {
+            if let Err(err) = check_pending_cap(conn, task_id) {
+                report.skipped.push(format!("{}: {}", describe(), err));
+                continue;
+            }
+        }
+
+        // Fired entries from exports that predate delivery tracking are assumed delivered when
+        // due, as the delivered_at migration does
+        let (delivered_via, delivered_at) = if entry.triggered {
+            (entry.delivered_via.clone(), entry.delivered_at.clone().or_else(|| Some(remind_at.clone())))
+        } else {
+            (None, None)
+        };
+
+        let inserted = conn
+            .execute(
+                "INSERT OR IGNORE INTO reminders (task_id, remind_at, triggered, acknowledged, delivered_via, delivered_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![task_id, remind_at, entry.triggered, entry.acknowledged, delivered_via, delivered_at],
+            )
+            .map_err(|e| e.to_string())?;
+        if inserted == 0 {
+            report.skipped.push(format!("{}: already scheduled", describe()));
+        } else {
+            report.imported += 1;
+        }
+    }
+
+    Ok(report)
+}
+
+// Reminders whose task no longer exists. check_and_fire joins on tasks, so these never fire;
+// they can only appear from old data or a connection that ran without foreign keys.
+const ORPHAN_REMINDER_IDS: &str =
+    "SELECT reminders.id FROM reminders LEFT JOIN tasks ON tasks.id = reminders.task_id WHERE tasks.id IS NULL";
+
+pub fn find_orphan_reminders(conn: &Connection) -> Result<Vec<Reminder>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM reminders WHERE id IN ({}) ORDER BY id",
+            REMINDER_COLUMNS, ORPHAN_REMINDER_IDS
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let reminders = stmt
+        .query_map([], reminder_from_row)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(reminders)
+}
+
+pub fn delete_orphan_reminders(conn: &Connection) -> Result<u64, String> {
+    conn.execute(&format!("DELETE FROM reminders WHERE id IN ({})", ORPHAN_REMINDER_IDS), [])
+        .map(|removed| removed as u64)
+        .map_err(|e| e.to_string())
+}
+
+pub fn cleanup_old_reminders(conn: &Connection, days: i64) -> Result<u64, String> {
+    if days < 0 {
+        return Err("Days must not be negative".to_string());
+    }
+
+    let cutoff = chrono::Duration::try_days(days)
+        .and_then(|age| Utc::now().checked_sub_signed(age))
+        .ok_or_else(|| format!("An age of {} days is out of range", days))?;
+
+    // Only triggered reminders are candidates; pending ones are kept regardless of age
+    let mut stmt = conn
+        .prepare("SELECT id, remind_at FROM reminders WHERE triggered = 1")
+        .map_err(|e| e.to_string())?;
+
+    let rows = stmt
+        .query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    let mut removed = 0;
+    for (id, remind_at) in rows {
+        if normalize_datetime(&remind_at).is_some_and(|dt| dt < cutoff) {
+            removed += conn
+                .execute("DELETE FROM reminders WHERE id = ?1 AND triggered = 1", params![id])
+                .map_err(|e| e.to_string())? as u64;
+        }
+    }
+
+    Ok(removed)
+}
+
+pub fn remind_all_overdue(conn: &Connection, offset_minutes: i64) -> Result<Vec<Reminder>, String> {
+    if offset_minutes < 0 {
+        return Err("Offset must not be negative".to_string());
+    }
+
+    let remind_at = chrono::Duration::try_minutes(offset_minutes)
+        .and_then(|offset| Utc::now().checked_add_signed(offset))
+        .ok_or_else(|| format!("An offset of {} minutes is out of range", offset_minutes))?
+        .to_rfc3339();
+
+    // Overdue tasks that don't already have a reminder waiting to fire
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT t.id FROM tasks t
+             WHERE {}
+             AND NOT EXISTS (SELECT 1 FROM reminders r WHERE r.task_id = t.id AND r.triggered = 0)",
+            crate::OVERDUE_TASK_FILTER
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let task_ids = stmt
+        .query_map([], |row| row.get::<_, i64>(0))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    let mut created = Vec::new();
+    for task_id in task_ids {
+        match conn.execute(
+            "INSERT INTO reminders (task_id, remind_at) VALUES (?1, ?2)",
+            params![task_id, remind_at],
+        ) {
+            Ok(_) => created.push(load_reminder(conn, conn.last_insert_rowid()).map_err(|e| e.to_string())?),
+            Err(err) if is_unique_violation(&err) => continue,
+            Err(err) => return Err(err.to_string()),
+        }
+    }
+
+    Ok(created)
+}
+
+pub fn copy_reminders(conn: &Connection, from_task_id: i64, to_task_id: i64) -> Result<Vec<Reminder>, String> {
+    for task_id in [from_task_id, to_task_id] {
+        if !task_exists(conn, task_id).map_err(|e| e.to_string())? {
+            return Err(format!("Task {} not found", task_id));
+        }
+    }
+
+    let mut created = Vec::new();
+    for source in get_reminders_for_task(conn, from_task_id, false)? {
+        match conn.execute(
+            "INSERT INTO reminders (task_id, remind_at, triggered) VALUES (?1, ?2, 0)",
+            params![to_task_id, source.remind_at],
+        ) {
+            Ok(_) => created.push(load_reminder(conn, conn.last_insert_rowid()).map_err(|e| e.to_string())?),
+            Err(err) if is_unique_violation(&err) => continue,
+            Err(err) => return Err(err.to_string()),
+        }
+    }
+
+    Ok(created)
+}
+
+pub fn shift_task_reminders(conn: &Connection, task_id: i64, minutes: i64) -> Result<Vec<Reminder>, String> {
+    if !task_exists(conn, task_id).map_err(|e| e.to_string())? {
+        return Err("Task not found".to_string());
+    }
+
+    let out_of_range = || format!("A shift of {} minutes is out of range", minutes);
+    let delta = chrono::Duration::try_minutes(minutes).ok_or_else(out_of_range)?;
+    let mut shifts: Vec<(i64, DateTime<Utc>)> = get_reminders_for_task(conn, task_id, false)?
+        .into_iter()
+        .filter_map(|reminder| normalize_datetime(&reminder.remind_at).map(|dt| (reminder.id, dt)))
+        .collect();
+
+    // Move the reminders furthest along the shift first so they don't collide with each other
+    shifts.sort_by_key(|(_, dt)| *dt);
+    if minutes > 0 {
+        shifts.reverse();
+    }
+
+    for (id, remind_at_dt) in shifts {
+        let shifted = remind_at_dt.checked_add_signed(delta).ok_or_else(out_of_range)?.to_rfc3339();
+        match conn.execute(
+            "UPDATE reminders SET remind_at = ?1, triggered = 0 WHERE id = ?2",
+            params![shifted, id],
+        ) {
+            Ok(_) => {}
+            // Another reminder already sits at the target time, so this one is a duplicate
+            Err(err) if is_unique_violation(&err) => {
+                conn.execute("DELETE FROM reminders WHERE id = ?1", params![id])
+                    .map_err(|e| e.to_string())?;
+            }
+            Err(err) => return Err(err.to_string()),
+        }
+    }
+
+    get_reminders_for_task(conn, task_id, false)
+}
+
+// A cleared due date leaves nothing to keep reminders relative to. By default they keep their
+// absolute times; callers that pass `delete_reminders` drop them along with the due date.
+pub fn handle_cleared_due_date(conn: &Connection, task_id: i64, delete_reminders: bool) -> rusqlite::Result<()> {
+    if delete_reminders {
+        conn.execute("DELETE FROM reminders WHERE task_id = ?1", params![task_id])?;
+    }
+    Ok(())
+}
+
 pub fn recalculate_reminders_for_task(
     conn: &Connection,
     task_id: i64,
@@ -148,30 +741,154 @@ pub fn recalculate_reminders_for_task(
 
 pub fn start_reminder_worker(app_handle: AppHandle, db: DatabaseConnection) {
     thread::spawn(move || {
-        if let Err(err) = check_and_fire(&app_handle, &db) {
-            eprintln!("reminder check failed: {}", err);
-        }
+        run_worker_pass(&app_handle, &db);
 
         loop {
-            thread::sleep(Duration::from_secs(CHECK_INTERVAL_SECS));
-            if let Err(err) = check_and_fire(&app_handle, &db) {
-                eprintln!("reminder check failed: {}", err);
-            }
+            thread::sleep(time_until_next_pass(&db));
+            run_worker_pass(&app_handle, &db);
         }
     });
 }
 
+// Wakes early when the next reminder comes due before the regular check, so it fires on time;
+// never sleeps longer than CHECK_INTERVAL_SECS, which the digest and liveness check rely on
+fn time_until_next_pass(db: &DatabaseConnection) -> Duration {
+    let interval = Duration::from_secs(CHECK_INTERVAL_SECS);
+    if crate::encryption::is_locked() {
+        return interval;
+    }
+
+    let next_at = db
+        .lock()
+        .ok()
+        .and_then(|conn| next_reminder(&conn).ok().flatten())
+        .and_then(|reminder| normalize_datetime(&reminder.remind_at));
+
+    // Reminders already due were just tried, and a failing one retries on the normal cadence
+    next_at
+        .and_then(|at| (at - Utc::now()).to_std().ok())
+        .map(|until_next| until_next.clamp(Duration::from_secs(1), interval))
+        .unwrap_or(interval)
+}
+
+fn run_worker_pass(app_handle: &AppHandle, db: &DatabaseConnection) {
+    LAST_WORKER_PASS.store(Utc::now().timestamp(), Ordering::Relaxed);
+    // Nothing to check until an encrypted database is unlocked
+    if crate::encryption::is_locked() {
+        return;
+    }
+    if let Err(err) = check_and_fire(app_handle, db) {
+        eprintln!("reminder check failed: {}", err);
+    }
+    if let Err(err) = send_daily_digest_if_due(app_handle, db) {
+        eprintln!("daily digest failed: {}", err);
+    }
+}
+
+// Once a day, at or after the configured local time ("HH:MM"), one notification summarizing
+// what is due today and what is overdue. Unset or empty means the digest is off.
+pub(crate) const DAILY_DIGEST_TIME_KEY: &str = "daily_digest_time";
+pub(crate) const DAILY_DIGEST_LAST_SENT_KEY: &str = "daily_digest_last_sent";
+
+fn send_daily_digest_if_due(app_handle: &AppHandle, db: &DatabaseConnection) -> Result<(), String> {
+    let conn = db.lock()?;
+
+    let digest_time = match crate::read_setting(&conn, DAILY_DIGEST_TIME_KEY)
+        .map_err(|e| e.to_string())?
+        .and_then(|value| NaiveTime::parse_from_str(&value, "%H:%M").ok())
+    {
+        Some(time) => time,
+        None => return Ok(()),
+    };
+
+    let now = Local::now();
+    let today = now.format("%Y-%m-%d").to_string();
+    let last_sent = crate::read_setting(&conn, DAILY_DIGEST_LAST_SENT_KEY).map_err(|e| e.to_string())?;
+    if now.time() < digest_time || last_sent.as_deref() == Some(today.as_str()) {
+        return Ok(());
+    }
+
+    // Due dates are local "%Y-%m-%dT%H:%M", so today's all share the date prefix
+    let mut stmt = conn
+        .prepare(
+            "SELECT title FROM tasks
+             WHERE status != 'Done' AND substr(due_date, 1, 10) = ?1
+             ORDER BY due_date, id",
+        )
+        .map_err(|e| e.to_string())?;
+    let due_today = stmt
+        .query_map(params![&today], |row| row.get::<_, String>(0))
+        .map_err(|e| e.to_string())?
+        .collect::<rusqlite::Result<Vec<_>>>()
+        .map_err(|e| e.to_string())?;
+
+    let overdue: i64 = conn
+        .query_row(
+            &format!("SELECT COUNT(*) FROM tasks WHERE {}", crate::OVERDUE_TASK_FILTER),
+            [],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+
+    // Nothing to report is not worth a toast, but still counts as today's digest
+    if !due_today.is_empty() || overdue > 0 {
+        let mut body_parts: Vec<String> = due_today.iter().take(SUMMARY_TITLE_COUNT).cloned().collect();
+        if due_today.len() > SUMMARY_TITLE_COUNT {
+            body_parts.push(format!("and {} more", due_today.len() - SUMMARY_TITLE_COUNT));
+        }
+        if overdue > 0 {
+            body_parts.push(format!("{} overdue", overdue));
+        }
+
+        // A failed notification is retried on the next pass
+        app_handle
+            .notification()
+            .builder()
+            .title(format!("{} due today", due_today.len()))
+            .body(body_parts.join(" • "))
+            .show()
+            .map_err(|e| e.to_string())?;
+    }
+
+    crate::write_setting(&conn, DAILY_DIGEST_LAST_SENT_KEY, &today).map_err(|e| e.to_string())
+}
+
+// Alive if the worker has run a pass within the last few intervals
+pub fn worker_is_alive() -> bool {
+    let last_pass = LAST_WORKER_PASS.load(Ordering::Relaxed);
+    last_pass > 0 && Utc::now().timestamp() - last_pass <= (CHECK_INTERVAL_SECS * 3) as i64
+}
+
+// For when the database behind the worker changes, e.g. a profile switch
+pub fn forget_recent_notifications() {
+    if let Ok(mut recently_fired) = RECENTLY_FIRED.lock() {
+        recently_fired.clear();
+    }
+}
+
+pub fn worker_status() -> WorkerStatus {
+    let last_pass = LAST_WORKER_PASS.load(Ordering::Relaxed);
+    WorkerStatus {
+        alive: worker_is_alive(),
+        last_run: Some(last_pass)
+            .filter(|last_pass| *last_pass > 0)
+            .and_then(|last_pass| Utc.timestamp_opt(last_pass, 0).single())
+            .map(|dt| dt.to_rfc3339()),
+        check_interval_secs: CHECK_INTERVAL_SECS,
+    }
+}
+
 fn check_and_fire(app_handle: &AppHandle, db: &DatabaseConnection) -> Result<(), String> {
-    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    let conn = db.lock()?;
     let now = Utc::now();
 
     let mut stmt = conn
-        .prepare(
+        .prepare(&format!(
             "SELECT r.id, r.task_id, r.remind_at, t.title, t.due_date, t.priority, t.tags
-             FROM reminders r
-             INNER JOIN tasks t ON t.id = r.task_id
+             {}
              WHERE r.triggered = 0",
-        )
+            REMINDER_TASK_JOIN
+        ))
         .map_err(|e| e.to_string())?;
 
     let rows = stmt
@@ -188,47 +905,201 @@ fn check_and_fire(app_handle: &AppHandle, db: &DatabaseConnection) -> Result<(),
         })
         .map_err(|e| e.to_string())?;
 
+    let mut due: Vec<PendingReminderRow> = Vec::new();
+
+    // A bad row or failed notification is logged and skipped so the rest still fire
     for row in rows {
-        let reminder = row.map_err(|e| e.to_string())?;
+        let reminder = match row {
+            Ok(reminder) => reminder,
+            Err(err) => {
+                eprintln!("skipping unreadable reminder row: {}", err);
+                continue;
+            }
+        };
+
         let remind_at_dt = match normalize_datetime(&reminder.remind_at) {
             Some(dt) => dt,
             None => {
-                conn.execute("UPDATE reminders SET triggered = 1 WHERE id = ?1", params![reminder.id])
-                    .map_err(|e| e.to_string())?;
+                // Retired so it isn't re-read every pass; a failure just retries next time
+                if let Err(err) = conn.execute("UPDATE reminders SET triggered = 1 WHERE id = ?1", params![reminder.id]) {
+                    eprintln!("failed to retire reminder {} with unreadable time: {}", reminder.id, err);
+                }
                 continue;
             }
         };
 
-        if remind_at_dt > now {
-            continue;
+        if remind_at_dt <= now {
+            due.push(reminder);
+        }
+    }
+
+    let mut recently_fired = RECENTLY_FIRED.lock().map_err(|e| e.to_string())?;
+    recently_fired.retain(|(_, _, shown_at, _)| now.timestamp() - shown_at < DUPLICATE_WINDOW_SECS);
+    due.retain(|reminder| {
+        let already_shown = recently_fired
+            .iter()
+            .find(|(id, remind_at, _, _)| *id == reminder.id && *remind_at == reminder.remind_at)
+            .map(|(_, _, shown_at, channel)| (*shown_at, *channel));
+        if let Some((shown_at, channel)) = already_shown {
+            // Still untriggered, so the earlier update failed; try it again without a new toast
+            let delivered_at = Utc.timestamp_opt(shown_at, 0).single().unwrap_or(now);
+            mark_triggered(&conn, reminder.id, channel, delivered_at);
+        }
+        already_shown.is_none()
+    });
+
+    let threshold = crate::read_setting(&conn, GROUP_THRESHOLD_KEY)
+        .map_err(|e| e.to_string())?
+        .and_then(|value| value.parse::<usize>().ok())
+        .unwrap_or(DEFAULT_GROUP_THRESHOLD);
+
+    if due.len() > threshold {
+        // Only a delivered summary keeps the batch triggered; a failure retries next pass
+        let ids: Vec<i64> = due.iter().map(|reminder| reminder.id).collect();
+        let channel = match claim_and_deliver(&conn, &ids, now, || send_summary_notification(app_handle, &due)) {
+            Ok(channel) => channel,
+            Err(err) => {
+                eprintln!("grouped reminder notification failed: {}", err);
+                return Ok(());
+            }
+        };
+
+        for reminder in &due {
+            recently_fired.push((reminder.id, reminder.remind_at.clone(), now.timestamp(), channel));
         }
+        return Ok(());
+    }
 
+    for reminder in &due {
         let due_dt = reminder.due_date.as_deref().and_then(normalize_datetime);
         let late = due_dt.map(|d| d < now).unwrap_or(false);
 
-        send_notification(app_handle, &reminder, due_dt, late)?;
+        // Only a delivered reminder stays triggered; when every channel fails it retries next pass
+        let show = || send_notification(app_handle, reminder, due_dt, late);
+        let channel = match claim_and_deliver(&conn, &[reminder.id], now, show) {
+            Ok(channel) => channel,
+            Err(err) => {
+                eprintln!("reminder {} notification failed: {}", reminder.id, err);
+                continue;
+            }
+        };
 
-        conn.execute(
-            "UPDATE reminders SET triggered = 1 WHERE id = ?1",
-            params![reminder.id],
-        )
-        .map_err(|e| e.to_string())?;
+        recently_fired.push((reminder.id, reminder.remind_at.clone(), now.timestamp(), channel));
     }
 
     Ok(())
 }
 
+// Sets triggered before anything is shown, in the same transaction as the delivery details, so
+// a failure after a successful show can't bring the reminder back next pass. The claim is rolled
+// back when no channel delivers. Once shown, failed bookkeeping is only logged; RECENTLY_FIRED
+// covers a commit that doesn't land.
+fn claim_and_deliver(
+    conn: &Connection,
+    reminder_ids: &[i64],
+    delivered_at: DateTime<Utc>,
+    show: impl FnOnce() -> Result<DeliveryChannel, String>,
+) -> Result<DeliveryChannel, String> {
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+    for id in reminder_ids {
+        tx.execute("UPDATE reminders SET triggered = 1 WHERE id = ?1", params![id])
+            .map_err(|e| e.to_string())?;
+    }
+
+    let channel = show()?;
+
+    for id in reminder_ids {
+        if let Err(err) = tx.execute(
+            "UPDATE reminders SET delivered_via = ?2, delivered_at = ?3 WHERE id = ?1",
+            params![id, channel.as_str(), delivered_at.to_rfc3339()],
+        ) {
+            eprintln!("failed to record delivery of reminder {}: {}", id, err);
+        }
+    }
+    if let Err(err) = tx.commit() {
+        eprintln!("failed to mark reminders {:?} triggered: {}", reminder_ids, err);
+    }
+
+    Ok(channel)
+}
+
+// Retries the bookkeeping for a reminder that was delivered but is still untriggered; rows
+// triggered any other way (an unreadable remind_at) keep a NULL delivered_at
+fn mark_triggered(conn: &Connection, reminder_id: i64, channel: DeliveryChannel, delivered_at: DateTime<Utc>) {
+    if let Err(err) = conn.execute(
+        "UPDATE reminders SET triggered = 1, delivered_via = ?2, delivered_at = ?3 WHERE id = ?1",
+        params![reminder_id, channel.as_str(), delivered_at.to_rfc3339()],
+    ) {
+        eprintln!("failed to mark reminder {} triggered: {}", reminder_id, err);
+    }
+}
+
+// Falls back to an in-app toast when the OS notification fails (permission denied, no
+// notification daemon). emit succeeds even with no window to receive it, so the toast only
+// counts as delivered while a window is open; otherwise the reminder retries next pass.
+fn deliver(
+    app_handle: &AppHandle,
+    reminder_ids: Vec<i64>,
+    title: String,
+    body: String,
+    show_os: impl FnOnce(String, String) -> Result<(), String>,
+) -> Result<DeliveryChannel, String> {
+    let os_err = match show_os(title.clone(), body.clone()) {
+        Ok(()) => return Ok(DeliveryChannel::Os),
+        Err(err) => err,
+    };
+    if app_handle.webview_windows().is_empty() {
+        return Err(format!("OS notification: {}; in-app toast: no open window", os_err));
+    }
+    eprintln!("OS notification failed, falling back to in-app toast: {}", os_err);
+
+    app_handle
+        .emit(REMINDER_TOAST_EVENT, ReminderToast { reminder_ids, title, body })
+        .map(|_| DeliveryChannel::InApp)
+        .map_err(|e| format!("OS notification: {}; in-app toast: {}", os_err, e))
+}
+
+fn send_summary_notification(app_handle: &AppHandle, reminders: &[PendingReminderRow]) -> Result<DeliveryChannel, String> {
+    let mut titles: Vec<String> = reminders
+        .iter()
+        .take(SUMMARY_TITLE_COUNT)
+        .map(|reminder| reminder.title.clone())
+        .collect();
+
+    if reminders.len() > SUMMARY_TITLE_COUNT {
+        titles.push(format!("and {} more", reminders.len() - SUMMARY_TITLE_COUNT));
+    }
+
+    deliver(
+        app_handle,
+        reminders.iter().map(|reminder| reminder.id).collect(),
+        format!("{} tasks need attention", reminders.len()),
+        titles.join(" • "),
+        |title, body| {
+            app_handle
+                .notification()
+                .builder()
+                .title(title)
+                .body(body)
+                .show()
+                .map_err(|e| e.to_string())
+        },
+    )
+}
+
 fn send_notification(
     app_handle: &AppHandle,
     reminder: &PendingReminderRow,
     due_dt: Option<DateTime<Utc>>,
     late: bool,
-) -> Result<(), String> {
+) -> Result<DeliveryChannel, String> {
     let mut body_parts: Vec<String> = Vec::new();
 
     if let Some(due) = due_dt {
         let local_due = due.with_timezone(&Local);
-        body_parts.push(format!("Task due at {}", local_due.format("%H:%M")));
+        // Only show seconds when the time actually has them
+        let time_format = if local_due.second() == 0 { "%H:%M" } else { "%H:%M:%S" };
+        body_parts.push(format!("Task due at {}", local_due.format(time_format)));
     }
 
     if !reminder.priority.is_empty() {
@@ -251,37 +1122,56 @@ fn send_notification(
         body_parts.join(" • ")
     };
 
-    app_handle
-        .notification()
-        .builder()
-        .title(reminder.title.clone())
-        .body(body)
-        .show()
-        .map_err(|e| e.to_string())?;
+    deliver(app_handle, vec![reminder.id], reminder.title.clone(), body, |title, body| {
+        let builder = app_handle.notification().builder().title(title).body(body);
 
-    Ok(())
+        // The plugin has no urgency level, so priority maps to sound: High plays one, Low is
+        // silent where the platform supports it, and anything else keeps the system default
+        let builder = match reminder.priority.as_str() {
+            "High" => builder.sound(URGENT_SOUND),
+            "Low" => builder.silent(),
+            _ => builder,
+        };
+
+        builder.show().map_err(|e| e.to_string())
+    })
 }
 
-fn normalize_datetime(raw: &str) -> Option<DateTime<Utc>> {
+pub(crate) fn normalize_datetime(raw: &str) -> Option<DateTime<Utc>> {
     if let Ok(dt) = DateTime::parse_from_rfc3339(raw) {
         return Some(dt.with_timezone(&Utc));
     }
 
+    if let Ok(dt) = NaiveDateTime::parse_from_str(raw, "%Y-%m-%dT%H:%M:%S") {
+        return resolve_local_datetime(&Local, dt);
+    }
+
     if let Ok(dt) = NaiveDateTime::parse_from_str(raw, "%Y-%m-%dT%H:%M") {
-        if let Some(local_dt) = Local.from_local_datetime(&dt).single() {
-            return Some(local_dt.with_timezone(&Utc));
-        }
+        return resolve_local_datetime(&Local, dt);
     }
 
     if let Ok(dt) = NaiveDateTime::parse_from_str(raw, "%Y-%m-%d %H:%M:%S") {
-        if let Some(local_dt) = Local.from_local_datetime(&dt).single() {
-            return Some(local_dt.with_timezone(&Utc));
-        }
+        return resolve_local_datetime(&Local, dt);
     }
 
     None
 }
 
+// Wall-clock times around DST changes: an ambiguous (fall-back) time resolves to its first
+// occurrence, and a time inside a spring-forward gap to the first instant after the gap.
+pub(crate) fn resolve_local_datetime<Tz: TimeZone>(tz: &Tz, naive: NaiveDateTime) -> Option<DateTime<Utc>> {
+    if let Some(dt) = tz.from_local_datetime(&naive).earliest() {
+        return Some(dt.with_timezone(&Utc));
+    }
+
+    // Gaps are at most a few hours, so stepping minute by minute finds the transition quickly
+    (1..=MAX_DST_GAP_MINUTES).find_map(|minutes| {
+        tz.from_local_datetime(&(naive + chrono::Duration::minutes(minutes)))
+            .earliest()
+            .map(|dt| dt.with_timezone(&Utc))
+    })
+}
+
 fn is_unique_violation(err: &rusqlite::Error) -> bool {
     matches!(
         err,
@@ -294,3 +1184,260 @@ fn is_unique_violation(err: &rusqlite::Error) -> bool {
         )
     ) || err.to_string().to_lowercase().contains("unique")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, LocalResult, NaiveDate};
+    use std::path::PathBuf;
+
+    fn test_db() -> Connection {
+        crate::init_database(PathBuf::from(":memory:"), None).expect("in-memory database")
+    }
+
+    fn add_reminder(conn: &Connection) -> i64 {
+        conn.execute(
+            "INSERT INTO tasks (title, description, tags, priority, status) VALUES ('Essay', '', '[]', 'High', 'Pending')",
+            [],
+        )
+        .unwrap();
+        let task_id = conn.last_insert_rowid();
+        conn.execute(
+            "INSERT INTO reminders (task_id, remind_at) VALUES (?1, '2026-03-01T09:00:00+00:00')",
+            params![task_id],
+        )
+        .unwrap();
+        conn.last_insert_rowid()
+    }
+
+    fn triggered(conn: &Connection, id: i64) -> bool {
+        conn.query_row("SELECT triggered FROM reminders WHERE id = ?1", params![id], |row| row.get(0))
+            .unwrap()
+    }
+
+    #[test]
+    fn failed_update_after_show_still_leaves_reminder_triggered() {
+        let conn = test_db();
+        let id = add_reminder(&conn);
+        conn.execute_batch(
+            "CREATE TRIGGER fail_delivery BEFORE UPDATE OF delivered_at ON reminders
+             BEGIN SELECT RAISE(ABORT, 'disk I/O error'); END",
+        )
+        .unwrap();
+
+        let mut shown = 0;
+        let channel = claim_and_deliver(&conn, &[id], Utc::now(), || {
+            shown += 1;
+            Ok(DeliveryChannel::Os)
+        });
+
+        assert!(matches!(channel, Ok(DeliveryChannel::Os)));
+        assert_eq!(shown, 1);
+        assert!(triggered(&conn, id), "a shown reminder must not be picked up again");
+    }
+
+    #[test]
+    fn failed_claim_shows_nothing() {
+        let conn = test_db();
+        let id = add_reminder(&conn);
+        conn.execute_batch(
+            "CREATE TRIGGER fail_claim BEFORE UPDATE OF triggered ON reminders
+             BEGIN SELECT RAISE(ABORT, 'database is locked'); END",
+        )
+        .unwrap();
+
+        let mut shown = false;
+        let result = claim_and_deliver(&conn, &[id], Utc::now(), || {
+            shown = true;
+            Ok(DeliveryChannel::Os)
+        });
+
+        assert!(result.is_err());
+        assert!(!shown);
+        assert!(!triggered(&conn, id));
+    }
+
+    #[test]
+    fn undelivered_reminder_is_left_for_retry() {
+        let conn = test_db();
+        let id = add_reminder(&conn);
+
+        let result = claim_and_deliver(&conn, &[id], Utc::now(), || Err("no notification daemon".to_string()));
+
+        assert!(result.is_err());
+        assert!(!triggered(&conn, id));
+    }
+
+    #[test]
+    fn delivered_reminder_records_its_channel() {
+        let conn = test_db();
+        let id = add_reminder(&conn);
+
+        claim_and_deliver(&conn, &[id], Utc::now(), || Ok(DeliveryChannel::InApp)).unwrap();
+
+        let via: Option<String> = conn
+            .query_row("SELECT delivered_via FROM reminders WHERE id = ?1", params![id], |row| row.get(0))
+            .unwrap();
+        assert!(triggered(&conn, id));
+        assert_eq!(via.as_deref(), Some(DeliveryChannel::InApp.as_str()));
+    }
+
+    #[test]
+    fn schedule_import_keeps_delivery_and_respects_the_cap() {
+        let conn = test_db();
+        add_reminder(&conn);
+        crate::write_setting(&conn, MAX_PENDING_PER_TASK_KEY, "2").unwrap();
+
+        let entry = |remind_at: &str, triggered: bool| ScheduledReminder {
+            task_id: None,
+            task_title: "Essay".to_string(),
+            remind_at: remind_at.to_string(),
+            triggered,
+            acknowledged: false,
+            delivered_via: triggered.then(|| DeliveryChannel::InApp.as_str().to_string()),
+            delivered_at: triggered.then(|| "2026-02-01T09:00:05+00:00".to_string()),
+        };
+        let report = import_reminder_schedule(
+            &conn,
+            &[
+                entry("2026-02-01T09:00:00+00:00", true),
+                entry("2026-04-01T09:00:00+00:00", false),
+                entry("2026-05-01T09:00:00+00:00", false),
+            ],
+        )
+        .unwrap();
+
+        // The existing pending reminder plus one import reach the cap of two; the fired one doesn't count
+        assert_eq!(report.imported, 2);
+        assert_eq!(report.skipped.len(), 1);
+
+        let (via, at): (Option<String>, Option<String>) = conn
+            .query_row(
+                "SELECT delivered_via, delivered_at FROM reminders WHERE triggered = 1",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(via.as_deref(), Some(DeliveryChannel::InApp.as_str()));
+        assert_eq!(at.as_deref(), Some("2026-02-01T09:00:05+00:00"));
+    }
+
+    // A zone shaped like Central European time in 2026: UTC+1, and UTC+2 from 29 March 02:00
+    // (clocks jump to 03:00) until 25 October 03:00 (clocks go back to 02:00)
+    #[derive(Clone)]
+    struct TestZone;
+
+    impl TestZone {
+        fn standard() -> FixedOffset {
+            FixedOffset::east_opt(3600).unwrap()
+        }
+
+        fn summer() -> FixedOffset {
+            FixedOffset::east_opt(2 * 3600).unwrap()
+        }
+    }
+
+    fn utc(y: i32, m: u32, d: u32, h: u32, min: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap().and_hms_opt(h, min, 0).unwrap()
+    }
+
+    impl TimeZone for TestZone {
+        type Offset = FixedOffset;
+
+        fn from_offset(_: &FixedOffset) -> Self {
+            TestZone
+        }
+
+        fn offset_from_local_date(&self, local: &NaiveDate) -> LocalResult<FixedOffset> {
+            self.offset_from_local_datetime(&local.and_hms_opt(0, 0, 0).unwrap())
+        }
+
+        fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> LocalResult<FixedOffset> {
+            // Earlier instant first, as chrono's own zones order an ambiguous result
+            let valid: Vec<FixedOffset> = [Self::summer(), Self::standard()]
+                .into_iter()
+                .filter(|offset| self.offset_from_utc_datetime(&(*local - *offset)) == *offset)
+                .collect();
+            match valid[..] {
+                [offset] => LocalResult::Single(offset),
+                [first, second] => LocalResult::Ambiguous(first, second),
+                _ => LocalResult::None,
+            }
+        }
+
+        fn offset_from_utc_date(&self, utc: &NaiveDate) -> FixedOffset {
+            self.offset_from_utc_datetime(&utc.and_hms_opt(0, 0, 0).unwrap())
+        }
+
+        fn offset_from_utc_datetime(&self, at: &NaiveDateTime) -> FixedOffset {
+            if *at >= utc(2026, 3, 29, 1, 0) && *at < utc(2026, 10, 25, 1, 0) {
+                Self::summer()
+            } else {
+                Self::standard()
+            }
+        }
+    }
+
+    fn local(raw: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(raw, "%Y-%m-%dT%H:%M").unwrap()
+    }
+
+    #[test]
+    fn ordinary_local_time_resolves_to_its_offset() {
+        assert_eq!(resolve_local_datetime(&TestZone, local("2026-01-15T09:00")), Some(utc(2026, 1, 15, 8, 0).and_utc()));
+        assert_eq!(resolve_local_datetime(&TestZone, local("2026-06-15T09:00")), Some(utc(2026, 6, 15, 7, 0).and_utc()));
+    }
+
+    #[test]
+    fn time_in_spring_forward_gap_moves_to_end_of_gap() {
+        // 02:00-02:59 never happens on 29 March; the first valid instant is 03:00 summer time
+        let expected = Some(utc(2026, 3, 29, 1, 0).and_utc());
+        assert_eq!(resolve_local_datetime(&TestZone, local("2026-03-29T02:00")), expected);
+        assert_eq!(resolve_local_datetime(&TestZone, local("2026-03-29T02:30")), expected);
+        assert_eq!(resolve_local_datetime(&TestZone, local("2026-03-29T03:00")), expected);
+    }
+
+    #[test]
+    fn time_in_fall_back_overlap_uses_first_occurrence() {
+        // 02:00-02:59 happens twice on 25 October; the summer time one comes first
+        assert_eq!(resolve_local_datetime(&TestZone, local("2026-10-25T02:30")), Some(utc(2026, 10, 25, 0, 30).and_utc()));
+        assert_eq!(resolve_local_datetime(&TestZone, local("2026-10-25T03:00")), Some(utc(2026, 10, 25, 2, 0).and_utc()));
+    }
+
+    fn as_local(dt: DateTime<Utc>) -> NaiveDateTime {
+        dt.with_timezone(&Local).naive_local()
+    }
+
+    #[test]
+    fn local_time_with_seconds_keeps_them() {
+        let parsed = normalize_datetime("2026-06-15T14:30:45").expect("seconds format");
+        let expected = NaiveDateTime::parse_from_str("2026-06-15T14:30:45", "%Y-%m-%dT%H:%M:%S").unwrap();
+        assert_eq!(as_local(parsed), expected);
+        assert_eq!(parsed.second(), 45);
+
+        let spaced = normalize_datetime("2026-06-15 14:30:45").expect("space-separated format");
+        assert_eq!(spaced, parsed);
+    }
+
+    #[test]
+    fn local_time_without_seconds_still_parses() {
+        let parsed = normalize_datetime("2026-06-15T14:30").expect("minute format");
+        assert_eq!(as_local(parsed), local("2026-06-15T14:30"));
+        assert_eq!(normalize_datetime("2026-06-15T14:30:00"), Some(parsed));
+    }
+
+    #[test]
+    fn rfc3339_seconds_are_kept() {
+        assert_eq!(
+            normalize_datetime("2026-06-15T14:30:45+02:00"),
+            Some((utc(2026, 6, 15, 12, 30) + chrono::Duration::seconds(45)).and_utc())
+        );
+    }
+
+    #[test]
+    fn malformed_times_are_rejected() {
+        assert_eq!(normalize_datetime("2026-06-15T14:30:61"), None);
+        assert_eq!(normalize_datetime("2026-06-15T14"), None);
+        assert_eq!(normalize_datetime(""), None);
+    }
+}
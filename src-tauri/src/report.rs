@@ -0,0 +1,91 @@
+use printpdf::{BuiltinFont, IndirectFontRef, Mm, PdfDocument, PdfLayerReference};
+use std::fs::File;
+use std::io::BufWriter;
+
+const PAGE_WIDTH_MM: f32 = 210.0;
+const PAGE_HEIGHT_MM: f32 = 297.0;
+const MARGIN_MM: f32 = 20.0;
+const LINE_HEIGHT_MM: f32 = 6.0;
+const MAX_LISTED_TASKS: usize = 20;
+
+pub struct MonthlyReport {
+    pub year: i32,
+    pub month: u32,
+    pub completed_tasks: Vec<String>,
+    pub hours_by_category: Vec<(String, f64)>,
+    pub expense_total: f64,
+    pub income_total: f64,
+}
+
+struct PageWriter {
+    layer: PdfLayerReference,
+    font: IndirectFontRef,
+    bold: IndirectFontRef,
+    y: f32,
+}
+
+impl PageWriter {
+    fn heading(&mut self, text: &str) {
+        self.y -= LINE_HEIGHT_MM / 2.0;
+        self.layer.use_text(text, 14.0, Mm(MARGIN_MM), Mm(self.y), &self.bold);
+        self.y -= LINE_HEIGHT_MM * 1.5;
+    }
+
+    fn row(&mut self, label: &str, value: &str) {
+        self.layer.use_text(label, 11.0, Mm(MARGIN_MM), Mm(self.y), &self.font);
+        if !value.is_empty() {
+            self.layer.use_text(value, 11.0, Mm(PAGE_WIDTH_MM - MARGIN_MM - 40.0), Mm(self.y), &self.font);
+        }
+        self.y -= LINE_HEIGHT_MM;
+    }
+}
+
+pub fn write_monthly_report_pdf(report: &MonthlyReport, path: &str) -> Result<(), String> {
+    let title = format!("ZenTrack Monthly Report - {:04}-{:02}", report.year, report.month);
+    let (doc, page, layer) = PdfDocument::new(title.as_str(), Mm(PAGE_WIDTH_MM), Mm(PAGE_HEIGHT_MM), "Report");
+
+    let font = doc.add_builtin_font(BuiltinFont::Helvetica).map_err(|e| e.to_string())?;
+    let bold = doc.add_builtin_font(BuiltinFont::HelveticaBold).map_err(|e| e.to_string())?;
+
+    let mut writer = PageWriter {
+        layer: doc.get_page(page).get_layer(layer),
+        font,
+        bold,
+        y: PAGE_HEIGHT_MM - MARGIN_MM,
+    };
+
+    writer.layer.use_text(title.as_str(), 18.0, Mm(MARGIN_MM), Mm(writer.y), &writer.bold);
+    writer.y -= LINE_HEIGHT_MM * 2.0;
+
+    writer.heading(&format!("Completed tasks ({})", report.completed_tasks.len()));
+    if report.completed_tasks.is_empty() {
+        writer.row("No tasks completed this month", "");
+    }
+    // Keep the report to a single page
+    for task in report.completed_tasks.iter().take(MAX_LISTED_TASKS) {
+        writer.row(&format!("- {}", task), "");
+    }
+    if report.completed_tasks.len() > MAX_LISTED_TASKS {
+        writer.row(&format!("... and {} more", report.completed_tasks.len() - MAX_LISTED_TASKS), "");
+    }
+
+    writer.heading("Tracked hours by category");
+    if report.hours_by_category.is_empty() {
+        writer.row("No time tracked this month", "");
+    }
+    for (category, hours) in &report.hours_by_category {
+        writer.row(category, &format!("{:.1} h", hours));
+    }
+    let total_hours: f64 = report.hours_by_category.iter().map(|(_, hours)| hours).sum();
+    writer.row("Total", &format!("{:.1} h", total_hours));
+
+    writer.heading("Money");
+    writer.row("Income", &format!("{:.2}", report.income_total));
+    writer.row("Expenses", &format!("{:.2}", report.expense_total));
+    writer.row("Net", &format!("{:.2}", report.income_total - report.expense_total));
+
+    let file = File::create(path).map_err(|e| format!("Cannot create '{}': {}", path, e))?;
+    doc.save(&mut BufWriter::new(file)).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-import { Routes, Route } from "react-router-dom";
+import { Routes, Route, useLocation, useNavigate } from "react-router-dom";
 import { useEffect } from "react";
 import Navbar from "./components/Navbar";
 import Landing from "./pages/Landing";
@@ -11,6 +11,7 @@ import SemesterView from "./pages/SemesterView";
 import { ThemeProvider } from "./context/ThemeContext";
 import { useAppStore } from "./state/appStore";
 import SemesterSetupWizard from "./components/SemesterSetupWizard";
+import ReminderToasts from "./components/ReminderToasts";
 import { invoke } from "@tauri-apps/api/core";
 
 function App() {
@@ -23,6 +24,23 @@ function App() {
     setSetupCompleted,
     setShowSetupWizard,
   } = useAppStore();
+  const location = useLocation();
+  const navigate = useNavigate();
+
+  // Reopen the view that was active when the app was last closed
+  useEffect(() => {
+    invoke<string>('get_last_view')
+      .then(view => navigate(`/${view}`, { replace: true }))
+      .catch(console.error);
+  }, [navigate]);
+
+  // Remember the active view; paths that aren't views (like the landing page) are ignored
+  useEffect(() => {
+    const view = location.pathname.replace(/^\//, '');
+    if (view) {
+      invoke('set_last_view', { view }).catch(() => {});
+    }
+  }, [location.pathname]);
 
   // Load data on mount
   useEffect(() => {
@@ -84,6 +102,8 @@ function App() {
         
         {/* Semester Setup Wizard */}
         {showSetupWizard && !setupCompleted && <SemesterSetupWizard />}
+
+        <ReminderToasts />
       </div>
     </ThemeProvider>
   );
@@ -1,5 +1,6 @@
 import { useEffect, useMemo, useState } from "react";
 import { useReminderStore } from "../state/reminderStore";
+import { getWorkerStatus, type WorkerStatus } from "../lib/reminders";
 
 const presets = [
   { label: "5 min before", minutes: 5 },
@@ -28,6 +29,7 @@ export default function ReminderPanel({ taskId, dueDate }: Props) {
   const { reminders, loading, load, add, remove } = useReminderStore();
   const [customInput, setCustomInput] = useState("");
   const [error, setError] = useState<string | null>(null);
+  const [workerStatus, setWorkerStatus] = useState<WorkerStatus | null>(null);
 
   useEffect(() => {
     if (taskId) {
@@ -35,6 +37,10 @@ export default function ReminderPanel({ taskId, dueDate }: Props) {
     }
   }, [taskId, load]);
 
+  useEffect(() => {
+    getWorkerStatus().then(setWorkerStatus).catch(console.error);
+  }, []);
+
   const canUsePresets = useMemo(() => {
     if (!dueDate) return false;
     const dt = new Date(dueDate);
@@ -112,6 +118,22 @@ export default function ReminderPanel({ taskId, dueDate }: Props) {
         )}
       </div>
 
+      {/* Reminders are only delivered while the background worker is running */}
+      {workerStatus && !workerStatus.alive && (
+        <div 
+          className="text-xs mb-3 rounded-lg px-3 py-2"
+          style={{ 
+            color: 'var(--danger)', 
+            backgroundColor: 'rgba(239, 68, 68, 0.1)',
+            border: '1px solid rgba(239, 68, 68, 0.2)'
+          }}
+        >
+          Reminders aren't being checked right now
+          {workerStatus.last_run ? ` (last check ${formatDate(workerStatus.last_run)})` : ""}.
+          Restart ZenTrack to resume them.
+        </div>
+      )}
+
       {/* Preset buttons */}
       <div className="grid grid-cols-2 gap-2 mb-4">
         {presets.map((preset) => (
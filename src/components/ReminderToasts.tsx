@@ -0,0 +1,61 @@
+import { useEffect, useState } from "react";
+import { onReminderToast, type ReminderToast } from "../lib/reminders";
+
+const TOAST_DURATION_MS = 10_000;
+
+type ShownToast = ReminderToast & { key: number };
+
+// In-app fallback for reminders whose OS notification couldn't be shown
+export default function ReminderToasts() {
+  const [toasts, setToasts] = useState<ShownToast[]>([]);
+
+  useEffect(() => {
+    let nextKey = 0;
+    const unlisten = onReminderToast((toast) => {
+      const key = nextKey++;
+      setToasts((current) => [...current, { ...toast, key }]);
+      setTimeout(() => {
+        setToasts((current) => current.filter((shown) => shown.key !== key));
+      }, TOAST_DURATION_MS);
+    });
+    return () => {
+      unlisten.then((stop) => stop());
+    };
+  }, []);
+
+  const dismiss = (key: number) => {
+    setToasts((current) => current.filter((shown) => shown.key !== key));
+  };
+
+  if (toasts.length === 0) return null;
+
+  return (
+    <div className="fixed bottom-4 right-4 z-50 flex flex-col gap-2 w-80">
+      {toasts.map((toast) => (
+        <div
+          key={toast.key}
+          className="rounded-xl p-4 shadow-lg"
+          style={{
+            backgroundColor: 'var(--bg-card)',
+            border: '1px solid var(--border)',
+            color: 'var(--text-primary)'
+          }}
+        >
+          <div className="flex items-start justify-between gap-2">
+            <p className="font-semibold text-sm">{toast.title}</p>
+            <button
+              onClick={() => dismiss(toast.key)}
+              className="text-xs"
+              style={{ color: 'var(--text-muted)' }}
+            >
+              Dismiss
+            </button>
+          </div>
+          <p className="text-sm mt-1" style={{ color: 'var(--text-muted)' }}>
+            {toast.body}
+          </p>
+        </div>
+      ))}
+    </div>
+  );
+}
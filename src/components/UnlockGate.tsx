@@ -0,0 +1,73 @@
+import { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+
+// Holds the app back until an encrypted database has been unlocked with its passphrase
+export default function UnlockGate({ children }: { children: React.ReactNode }) {
+  const [locked, setLocked] = useState<boolean | null>(null);
+  const [passphrase, setPassphrase] = useState("");
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    invoke<boolean>("is_database_locked")
+      .then(setLocked)
+      .catch(() => setLocked(false));
+
+    // Switching to an encrypted profile, or a failed encryption change, locks the running app
+    const unlisten = listen("database-locked", () => setLocked(true));
+    return () => {
+      unlisten.then((stop) => stop());
+    };
+  }, []);
+
+  const handleUnlock = async (e: React.FormEvent) => {
+    e.preventDefault();
+    setError(null);
+    try {
+      await invoke("unlock_database", { passphrase });
+      setPassphrase("");
+      setLocked(false);
+    } catch (err: unknown) {
+      setError(String(err));
+    }
+  };
+
+  if (locked === null) return null;
+  if (!locked) return <>{children}</>;
+
+  return (
+    <div
+      className="min-h-screen flex items-center justify-center"
+      style={{ backgroundColor: 'var(--bg-primary)', color: 'var(--text-primary)' }}
+    >
+      <form
+        onSubmit={handleUnlock}
+        className="rounded-xl p-6 w-full max-w-sm"
+        style={{
+          backgroundColor: 'var(--bg-card)',
+          border: '1px solid var(--border)'
+        }}
+      >
+        <h2 className="text-lg font-semibold mb-2">ZenTrack is locked</h2>
+        <p className="text-sm mb-4" style={{ color: 'var(--text-muted)' }}>
+          Enter your passphrase to open your data.
+        </p>
+        <input
+          type="password"
+          value={passphrase}
+          onChange={(e) => setPassphrase(e.target.value)}
+          className="input w-full mb-3"
+          autoFocus
+        />
+        {error && (
+          <p className="text-sm mb-3" style={{ color: 'var(--danger)' }}>
+            {error}
+          </p>
+        )}
+        <button type="submit" className="btn btn-primary w-full" disabled={!passphrase}>
+          Unlock
+        </button>
+      </form>
+    </div>
+  );
+}
@@ -1,4 +1,5 @@
 import { createContext, useContext, useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
 
 type Theme = 'dark' | 'light';
 
@@ -15,6 +16,19 @@ export const ThemeProvider = ({ children }: { children: React.ReactNode }) => {
     return saved || 'dark';
   });
 
+  // The backend setting is the source of truth; localStorage only avoids a flash on startup
+  useEffect(() => {
+    invoke<'light' | 'dark' | 'system'>('get_theme')
+      .then((stored) => {
+        if (stored === 'system') {
+          setTheme(window.matchMedia('(prefers-color-scheme: light)').matches ? 'light' : 'dark');
+        } else {
+          setTheme(stored);
+        }
+      })
+      .catch(console.error);
+  }, []);
+
   useEffect(() => {
     const html = document.documentElement;
     
@@ -30,7 +44,9 @@ export const ThemeProvider = ({ children }: { children: React.ReactNode }) => {
   }, [theme]);
 
   const toggleTheme = () => {
-    setTheme(prev => prev === 'dark' ? 'light' : 'dark');
+    const next: Theme = theme === 'dark' ? 'light' : 'dark';
+    setTheme(next);
+    invoke('set_theme', { theme: next }).catch(console.error);
   };
 
   return (
@@ -0,0 +1,49 @@
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+
+export type BackupProgress = {
+  percent: number;
+  section: string;
+};
+
+async function withProgress(
+  event: string,
+  onProgress: ((progress: BackupProgress) => void) | undefined,
+  run: () => Promise<void>,
+): Promise<void> {
+  const unlisten = onProgress
+    ? await listen<BackupProgress>(event, (e) => onProgress(e.payload))
+    : undefined;
+  try {
+    await run();
+  } finally {
+    unlisten?.();
+  }
+}
+
+export async function exportAll(path: string, onProgress?: (progress: BackupProgress) => void): Promise<void> {
+  await withProgress("export-progress", onProgress, () => invoke("export_all", { path }));
+}
+
+export async function importAll(path: string, onProgress?: (progress: BackupProgress) => void): Promise<void> {
+  await withProgress("import-progress", onProgress, () => invoke("import_all", { path }));
+}
+
+export type AutoBackupSettings = {
+  enabled: boolean;
+  interval_hours: number;
+  dir: string | null;
+  keep: number;
+};
+
+export async function getAutoBackup(): Promise<AutoBackupSettings> {
+  return invoke<AutoBackupSettings>("get_auto_backup");
+}
+
+export async function setAutoBackup(enabled: boolean, intervalHours: number, dir: string, keep: number): Promise<void> {
+  await invoke("set_auto_backup", { enabled, intervalHours, dir, keep });
+}
+
+export async function getLastBackupTime(): Promise<string | null> {
+  return invoke<string | null>("get_last_backup_time");
+}
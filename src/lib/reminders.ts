@@ -1,4 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 
 export type Reminder = {
   id: number;
@@ -6,17 +7,114 @@ export type Reminder = {
   remind_at: string;
   triggered: boolean;
   created_at: string;
+  acknowledged: boolean;
+  delivered_via: "os" | "in_app" | null;
+  delivered_at: string | null;
 };
 
-export async function createReminder(taskId: number, remindAt: string): Promise<void> {
-  await invoke("create_reminder", { taskId, remindAt });
+export type ReminderWithTask = Reminder & {
+  task_title: string;
+  task_status: string;
+  task_priority: string;
+  task_due_date: string | null;
+};
+
+export type WorkerStatus = {
+  alive: boolean;
+  last_run: string | null;
+  check_interval_secs: number;
+};
+
+// Sent instead of an OS notification when that one can't be shown
+export type ReminderToast = {
+  reminder_ids: number[];
+  title: string;
+  body: string;
+};
+
+export async function onReminderToast(handler: (toast: ReminderToast) => void): Promise<UnlistenFn> {
+  return listen<ReminderToast>("reminder-toast", (e) => handler(e.payload));
 }
 
-export async function getReminders(taskId: number): Promise<Reminder[]> {
-  const res = await invoke<Reminder[]>("get_reminders_for_task", { taskId });
+export async function createReminder(taskId: number, remindAt: string, force = false): Promise<void> {
+  await invoke("create_reminder", { taskId, remindAt, force });
+}
+
+export async function getReminders(taskId: number, descending = false): Promise<Reminder[]> {
+  const res = await invoke<Reminder[]>("get_reminders_for_task", { taskId, descending });
   return res;
 }
 
 export async function deleteReminder(reminderId: number): Promise<void> {
   await invoke("delete_reminder", { reminderId });
 }
+
+export async function resetReminder(reminderId: number): Promise<void> {
+  await invoke("reset_reminder", { reminderId });
+}
+
+export async function acknowledgeReminder(reminderId: number): Promise<void> {
+  await invoke("acknowledge_reminder", { reminderId });
+}
+
+export async function getPendingReminders(includeUnacknowledged = false): Promise<ReminderWithTask[]> {
+  return invoke<ReminderWithTask[]>("get_pending_reminders", { includeUnacknowledged });
+}
+
+export async function remindAllOverdue(offsetMinutes: number): Promise<Reminder[]> {
+  return invoke<Reminder[]>("remind_all_overdue", { offsetMinutes });
+}
+
+export async function copyReminders(fromTaskId: number, toTaskId: number): Promise<Reminder[]> {
+  return invoke<Reminder[]>("copy_reminders", { fromTaskId, toTaskId });
+}
+
+export async function getUpcomingReminders(hours: number): Promise<ReminderWithTask[]> {
+  return invoke<ReminderWithTask[]>("get_tasks_with_upcoming_reminders", { hours });
+}
+
+export async function cleanupOldReminders(days: number): Promise<number> {
+  return invoke<number>("cleanup_old_reminders", { days });
+}
+
+export type ReminderImportReport = {
+  imported: number;
+  skipped: string[];
+};
+
+export async function getNextReminder(): Promise<ReminderWithTask | null> {
+  return invoke<ReminderWithTask | null>("get_next_reminder");
+}
+
+export async function exportReminders(path: string): Promise<void> {
+  await invoke("export_reminders", { path });
+}
+
+export async function importReminders(path: string): Promise<ReminderImportReport> {
+  return invoke<ReminderImportReport>("import_reminders", { path });
+}
+
+export async function findOrphanReminders(): Promise<Reminder[]> {
+  return invoke<Reminder[]>("find_orphan_reminders");
+}
+
+export async function deleteOrphanReminders(): Promise<number> {
+  return invoke<number>("delete_orphan_reminders");
+}
+
+export async function getRemindersDetailed(taskId: number): Promise<ReminderWithTask[]> {
+  return invoke<ReminderWithTask[]>("get_reminders_for_task_detailed", { taskId });
+}
+
+export async function shiftTaskReminders(taskId: number, minutes: number): Promise<Reminder[]> {
+  return invoke<Reminder[]>("shift_task_reminders", { taskId, minutes });
+}
+
+export async function getReminderCounts(): Promise<{ pending: number; triggered: number }> {
+  const [pending, triggered] = await invoke<[number, number]>("get_reminder_counts");
+  return { pending, triggered };
+}
+
+export async function getWorkerStatus(): Promise<WorkerStatus> {
+  return invoke<WorkerStatus>("get_worker_status");
+}
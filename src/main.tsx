@@ -2,12 +2,15 @@ import React from "react";
 import ReactDOM from "react-dom/client";
 import { BrowserRouter } from "react-router-dom";
 import App from "./App";
+import UnlockGate from "./components/UnlockGate";
 import "./index.css";
 
 ReactDOM.createRoot(document.getElementById("root") as HTMLElement).render(
   <React.StrictMode>
     <BrowserRouter>
-      <App />
+      <UnlockGate>
+        <App />
+      </UnlockGate>
     </BrowserRouter>
   </React.StrictMode>,
 );
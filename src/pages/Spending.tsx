@@ -19,6 +19,7 @@ const Spending = () => {
     type: 'expense' as 'expense' | 'income',
   });
   const [isLoading, setIsLoading] = useState(true);
+  const [budgetWarning, setBudgetWarning] = useState<string | null>(null);
   
   const expenseCategories = ['Food', 'Transport', 'Entertainment', 'Shopping', 'Bills', 'Health', 'Education', 'Other'];
   const incomeCategories = ['Salary', 'Freelance', 'Investment', 'Gift', 'Other'];
@@ -53,8 +54,16 @@ const Spending = () => {
         expense_type: form.type,
       };
 
-      const updatedExpenses = await invoke<Expense[]>('add_expense', { expense: newExpense });
-      setExpenses(updatedExpenses);
+      const result = await invoke<{
+        expenses: Expense[];
+        budget_warning: { category: string; overage: number } | null;
+      }>('add_expense', { expense: newExpense });
+      setExpenses(result.expenses);
+      setBudgetWarning(
+        result.budget_warning
+          ? `${result.budget_warning.category} budget exceeded by ${formatCurrency(result.budget_warning.overage)}`
+          : null
+      );
       setForm({ amount: '', description: '', category: form.type === 'expense' ? 'Food' : 'Salary', type: form.type });
     } catch (error) {
       console.error('Failed to add expense:', error);
@@ -267,6 +276,18 @@ const Spending = () => {
                 Add {form.type === 'expense' ? 'Expense' : 'Income'}
               </button>
             </form>
+
+            {budgetWarning && (
+              <div
+                className="mt-4 flex items-start justify-between gap-3 rounded-lg p-3 text-sm"
+                style={{ backgroundColor: 'rgba(239, 68, 68, 0.1)', color: 'var(--danger)' }}
+              >
+                <span>{budgetWarning}</span>
+                <button type="button" onClick={() => setBudgetWarning(null)} aria-label="Dismiss budget warning">
+                  ×
+                </button>
+              </div>
+            )}
           </div>
 
           {/* Category Breakdown */}
@@ -34,6 +34,11 @@ export type Task = {
   subject_id?: number;
   estimated_minutes?: number;
   actual_minutes?: number;
+  is_pinned?: boolean;
+  created_at?: string;
+  status_changed_at?: string;
+  completed_at?: string | null;
+  color?: string | null;
 };
 
 export type TimeEntry = {
@@ -44,6 +49,9 @@ export type TimeEntry = {
   duration: number;
   category: string;
   subject_id?: number;
+  task_id?: number;
+  archived?: boolean;
+  description?: string;
 };
 
 export type StudyStreak = {